@@ -0,0 +1,245 @@
+use crate::solution::DynSolution;
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+//Differences below this are timer noise, never flag them
+const NOISE_MS: f64 = 0.05;
+
+pub struct Config {
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            warmup: 3,
+            runs: 10,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.).collect();
+        ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let n = ms.len().max(1) as f64;
+        let mean = ms.iter().sum::<f64>() / n;
+        let var = ms.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        let median = match ms.len() {
+            0 => 0.,
+            len if len % 2 == 0 => (ms[len / 2 - 1] + ms[len / 2]) / 2.,
+            len => ms[len / 2],
+        };
+
+        Stats {
+            min: ms.first().cloned().unwrap_or(0.),
+            median,
+            mean,
+            stddev: var.sqrt(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DayBench {
+    pub day: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayBench {
+    pub fn parts(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ]
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub warmup: usize,
+    pub runs: usize,
+    pub days: Vec<DayBench>,
+}
+
+#[derive(Debug)]
+pub struct Regression {
+    pub day: usize,
+    pub part: &'static str,
+    pub baseline: f64,
+    pub current: f64,
+}
+
+fn measure<T>(cfg: &Config, mut f: impl FnMut() -> AocResult<T>) -> AocResult<Stats> {
+    for _ in 0..cfg.warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(cfg.runs);
+    for _ in 0..cfg.runs {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+
+    Ok(Stats::from_samples(&samples))
+}
+
+pub fn bench_day(solution: &dyn DynSolution, input: &str, cfg: &Config) -> AocResult<DayBench> {
+    let data = solution.parse(input)?;
+
+    Ok(DayBench {
+        day: solution.day(),
+        parse: measure(cfg, || solution.parse(input))?,
+        part1: measure(cfg, || solution.part1(data.as_ref()))?,
+        part2: measure(cfg, || solution.part2(data.as_ref()))?,
+    })
+}
+
+impl Report {
+    pub fn to_json(&self) -> AocResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| AocErr::Other(Box::new(e)))
+    }
+
+    pub fn from_json(s: &str) -> AocResult<Report> {
+        serde_json::from_str(s).map_err(|e| AocErr::Other(Box::new(e)))
+    }
+
+    pub fn to_markdown(&self, regressions: &[Regression]) -> String {
+        let mut s = String::new();
+        writeln!(s, "Warmup: {}, runs: {}\n", self.warmup, self.runs).unwrap();
+        writeln!(s, "| Day | Part | Min (ms) | Median (ms) | Stddev (ms) | |").unwrap();
+        writeln!(s, "|----:|------|---------:|------------:|------------:|-|").unwrap();
+
+        for day in self.days.iter() {
+            for (part, stats) in day.parts().iter() {
+                let flag = regressions
+                    .iter()
+                    .find(|r| r.day == day.day && r.part == *part)
+                    .map(|r| format!("regressed {:+.0}%", (r.current / r.baseline - 1.) * 100.))
+                    .unwrap_or_default();
+
+                writeln!(
+                    s,
+                    "| {:02} | {} | {:.3} | {:.3} | {:.3} | {} |",
+                    day.day, part, stats.min, stats.median, stats.stddev, flag
+                )
+                .unwrap();
+            }
+        }
+
+        s
+    }
+
+    //Flags parts whose median grew by more than `threshold` (0.1 = 10%)
+    pub fn compare(&self, baseline: &Report, threshold: f64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+
+        for day in self.days.iter() {
+            let base = match baseline.days.iter().find(|b| b.day == day.day) {
+                Some(base) => base,
+                None => continue,
+            };
+
+            for ((part, cur), (_, base)) in day.parts().iter().zip(base.parts().iter()) {
+                if cur.median > base.median * (1. + threshold)
+                    && cur.median - base.median > NOISE_MS
+                {
+                    regressions.push(Regression {
+                        day: day.day,
+                        part,
+                        baseline: base.median,
+                        current: cur.median,
+                    });
+                }
+            }
+        }
+
+        regressions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::registry;
+
+    fn stats(median: f64) -> Stats {
+        Stats {
+            min: median,
+            median,
+            mean: median,
+            stddev: 0.,
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let ms = |n| Duration::from_millis(n);
+        let s = Stats::from_samples(&[ms(4), ms(1), ms(3), ms(2)]);
+
+        assert_eq!(1., s.min);
+        assert_eq!(2.5, s.median);
+        assert_eq!(2.5, s.mean);
+        assert!((s.stddev - 1.25_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compare() -> AocResult<()> {
+        let day = |median| DayBench {
+            day: 1,
+            parse: stats(0.01),
+            part1: stats(median),
+            part2: stats(1.),
+        };
+
+        let baseline = Report {
+            warmup: 0,
+            runs: 1,
+            days: vec![day(1.)],
+        };
+        let baseline = Report::from_json(&baseline.to_json()?)?;
+
+        let current = Report {
+            days: vec![day(2.)],
+            ..baseline.clone()
+        };
+
+        let regs = current.compare(&baseline, 0.1);
+        assert_eq!(1, regs.len());
+        assert_eq!("part1", regs[0].part);
+        assert!(current.to_markdown(&regs).contains("regressed +100%"));
+        assert!(baseline.compare(&current, 0.1).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bench_day() -> AocResult<()> {
+        let registry = registry();
+        let input = std::fs::read_to_string(file_path(FileType::Input, 1, 1))?;
+        let cfg = Config { warmup: 1, runs: 3 };
+        let bench = bench_day(registry.get(1).unwrap(), &input, &cfg)?;
+
+        assert_eq!(1, bench.day);
+        assert!(bench.part2.min <= bench.part2.median);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use aoc19::bench::{bench_day, Config, Report};
+use aoc19::days::registry;
+use aoc19::solution::parse_days;
+use aoc19::{custom_err, file_path, AocResult, FileType};
+use std::fs;
+
+const USAGE: &str = "usage: bench [days...] [--warmup N] [--runs N] [--json FILE] [--md FILE]
+             [--baseline FILE] [--threshold FRACTION]";
+
+fn main() -> AocResult<()> {
+    let mut cfg = Config::default();
+    let mut days = Vec::new();
+    let (mut json, mut md, mut baseline) = (None, None, None);
+    let mut threshold = 0.1;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| custom_err(USAGE));
+        match arg.as_str() {
+            "--warmup" => cfg.warmup = value()?.parse()?,
+            "--runs" => cfg.runs = value()?.parse()?,
+            "--json" => json = Some(value()?),
+            "--md" => md = Some(value()?),
+            "--baseline" => baseline = Some(value()?),
+            "--threshold" => {
+                threshold = value()?
+                    .parse()
+                    .map_err(|_| custom_err("Invalid threshold"))?
+            }
+            a if a.starts_with("--") => return Err(custom_err(USAGE)),
+            _ => days.push(arg),
+        }
+    }
+
+    let registry = registry();
+    let known: Vec<usize> = registry.days().collect();
+
+    let mut report = Report {
+        warmup: cfg.warmup,
+        runs: cfg.runs,
+        days: Vec::new(),
+    };
+
+    for day in parse_days(&days, &known)? {
+        let input = match fs::read_to_string(file_path(FileType::Input, day, 1)) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("Day {:02}: no input: {}", day, err);
+                continue;
+            }
+        };
+
+        match bench_day(registry.get(day).unwrap(), &input, &cfg) {
+            Ok(bench) => {
+                eprintln!("Day {:02} done", day);
+                report.days.push(bench);
+            }
+            Err(err) => eprintln!("Day {:02}: {}", day, err),
+        }
+    }
+
+    let regressions = match baseline {
+        Some(p) => report.compare(&Report::from_json(&fs::read_to_string(p)?)?, threshold),
+        None => Vec::new(),
+    };
+
+    let table = report.to_markdown(&regressions);
+    println!("{}", table);
+
+    if let Some(p) = json {
+        fs::write(p, report.to_json()?)?;
+    }
+
+    if let Some(p) = md {
+        fs::write(p, table)?;
+    }
+
+    if !regressions.is_empty() {
+        return Err(custom_err(format!("{} regressions", regressions.len())));
+    }
+
+    Ok(())
+}
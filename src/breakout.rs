@@ -0,0 +1,476 @@
+use crate::days::day05::Data;
+use crate::days::day13::{Game, Input, Observation, Strategy, Tile};
+use crate::helper::grid::Grid;
+use crate::helper::render::{Palette, Renderer, Style, BLACK};
+use crate::helper::vec::Vec2;
+use crate::*;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+pub fn palette() -> Palette<Tile> {
+    Palette::new(BLACK)
+        .with(Tile::Wall, Style::new([128, 128, 128], '#'))
+        .with(Tile::Block, Style::new([200, 80, 40], '*'))
+        .with(Tile::HorizontalPaddle, Style::new([255, 255, 255], '_'))
+        .with(Tile::Ball, Style::new([255, 220, 0], 'O'))
+}
+
+pub fn renderer() -> Renderer<Palette<Tile>> {
+    Renderer::new(palette())
+}
+
+//Terminal glyph of a tile, walls are joined with box drawing characters
+pub fn box_glyph(tiles: &Grid<Tile>, (x, y): (usize, usize)) -> char {
+    match tiles[(x, y)] {
+        Tile::Empty => ' ',
+        Tile::Block => '▒',
+        Tile::HorizontalPaddle => '▀',
+        Tile::Ball => '●',
+        Tile::Wall => {
+            let wall =
+                |dx, dy| tiles.at(Vec2::new(x as isize + dx, y as isize + dy)) == Some(&Tile::Wall);
+            let ix = [(0, -1), (0, 1), (-1, 0), (1, 0)]
+                .iter()
+                .enumerate()
+                .filter(|(_, &(dx, dy))| wall(dx, dy))
+                .fold(0, |ix, (bit, _)| ix | 1 << bit);
+            //Indexed by up, down, left, right bits
+            "■│││─┘┐┤─└┌├─┴┬┼".chars().nth(ix).unwrap()
+        }
+    }
+}
+
+//Upper bound of simulated ball moves per prediction
+const MAX_SIM: usize = 10_000;
+
+//Simulates the ball bouncing off walls and blocks and waits where it comes down,
+//only moving the paddle when it has to
+pub struct Predictive;
+
+impl Predictive {
+    //Column of the ball when it is about to hit the paddle row
+    pub fn landing(obs: &Observation) -> Option<isize> {
+        let (mut pos, mut vel) = (obs.ball, obs.velocity);
+        let mut broken = HashSet::new();
+        let mut hit = |p: Vec2| match obs.tiles.at(p) {
+            Some(Tile::Wall) => true,
+            Some(Tile::Block) => broken.insert(p),
+            _ => false,
+        };
+
+        for _ in 0..MAX_SIM {
+            if pos.y >= obs.paddle.y {
+                return None;
+            }
+
+            if vel.y > 0 && pos.y + 1 == obs.paddle.y {
+                return Some(pos.x);
+            }
+
+            //The game keeps bouncing until the way ahead is free, then moves in the same tick
+            let mut bounced = false;
+            if hit(pos + Vec2::new(vel.x, 0)) {
+                vel.x = -vel.x;
+                bounced = true;
+            }
+            if hit(pos + Vec2::new(0, vel.y)) {
+                vel.y = -vel.y;
+                bounced = true;
+            }
+            if !bounced && hit(pos + vel) {
+                vel = -vel;
+                bounced = true;
+            }
+
+            if !bounced {
+                pos += vel;
+            }
+        }
+
+        None
+    }
+}
+
+impl Strategy for Predictive {
+    fn name(&self) -> &str {
+        "predictive"
+    }
+
+    fn input(&mut self, obs: &Observation) -> Input {
+        let target = Predictive::landing(obs).unwrap_or(obs.ball.x);
+        match target.cmp(&obs.paddle.x) {
+            Ordering::Equal => Input::Neutral,
+            Ordering::Greater => Input::Right,
+            Ordering::Less => Input::Left,
+        }
+    }
+}
+
+//Result of playing one game with a strategy
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub name: String,
+    pub score: usize,
+    pub ticks: usize,
+    //Non neutral inputs
+    pub moves: usize,
+    pub blocks_left: usize,
+}
+
+impl Outcome {
+    pub fn cleared(&self) -> bool {
+        self.blocks_left == 0
+    }
+}
+
+pub fn evaluate(data: &Data, strategy: &mut dyn Strategy, max_ticks: usize) -> AocResult<Outcome> {
+    let mut headless = Headless::new(Game::create(data.clone(), true)?);
+    let score = headless.run(strategy, max_ticks)?;
+    let replay = headless.replay();
+    Ok(Outcome {
+        name: strategy.name().to_string(),
+        score,
+        ticks: replay.len(),
+        moves: replay.moves(),
+        blocks_left: headless.game().blocks().count(),
+    })
+}
+
+//Plays a full game with every strategy, best first: cleared boards before
+//others, then fewer moves
+pub fn compare(
+    data: &Data,
+    strategies: &mut [&mut dyn Strategy],
+    max_ticks: usize,
+) -> AocResult<Vec<Outcome>> {
+    let mut outcomes = strategies
+        .iter_mut()
+        .map(|s| evaluate(data, *s, max_ticks))
+        .collect::<AocResult<Vec<_>>>()?;
+    outcomes.sort_by_key(|o| (o.blocks_left, o.moves, o.ticks));
+    Ok(outcomes)
+}
+
+pub fn report(outcomes: &[Outcome]) -> String {
+    let mut s = format!(
+        "{:<12} {:>8} {:>8} {:>8} {:>8}\n",
+        "strategy", "score", "ticks", "moves", "blocks"
+    );
+    for o in outcomes {
+        s += &format!(
+            "{:<12} {:>8} {:>8} {:>8} {:>8}\n",
+            o.name, o.score, o.ticks, o.moves, o.blocks_left
+        );
+    }
+    s
+}
+
+fn input_char(input: Input) -> char {
+    match input {
+        Input::Neutral => 'N',
+        Input::Left => 'L',
+        Input::Right => 'R',
+    }
+}
+
+fn parse_input(s: &str) -> AocResult<Input> {
+    match s {
+        "N" => Ok(Input::Neutral),
+        "L" => Ok(Input::Left),
+        "R" => Ok(Input::Right),
+        _ => Err(custom_err(format!("Invalid input: {}", s))),
+    }
+}
+
+//Joystick input of one tick and the score after it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub input: Input,
+    pub score: usize,
+}
+
+//Input log of a game, one `<N|L|R> <score>` line per tick
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Replay {
+    pub steps: Vec<Step>,
+}
+
+impl Replay {
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn score(&self) -> usize {
+        self.steps.last().map_or(0, |s| s.score)
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = Input> + '_ {
+        self.steps.iter().map(|s| s.input)
+    }
+
+    pub fn moves(&self) -> usize {
+        self.inputs().filter(|&i| i != Input::Neutral).count()
+    }
+
+    //Plays the log from the start, every step has to reproduce its score
+    //and the game has to end with the last one. Returns the final score
+    pub fn play(&self, data: Data) -> AocResult<usize> {
+        let mut game = Game::create(data, true)?;
+        for (ix, step) in self.steps.iter().enumerate() {
+            if game.halted() {
+                return Err(custom_err(format!(
+                    "Game over after {} of {} steps",
+                    ix,
+                    self.len()
+                )));
+            }
+
+            game.step(step.input)?;
+            if game.score() != step.score {
+                return Err(custom_err(format!(
+                    "Step {}: expected score {}, got {}",
+                    ix,
+                    step.score,
+                    game.score()
+                )));
+            }
+        }
+
+        if !game.halted() {
+            return Err(custom_err(format!(
+                "Game still running after {} steps",
+                self.len()
+            )));
+        }
+
+        Ok(game.score())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> AocResult<()> {
+        Ok(std::fs::write(path, self.to_string())?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> AocResult<Replay> {
+        std::fs::read_to_string(path)?.parse()
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{} {}", input_char(step.input), step.score)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Replay {
+    type Err = AocErr;
+
+    fn from_str(s: &str) -> AocResult<Self> {
+        let mut steps = Vec::new();
+        for (ix, line) in s.lines().enumerate() {
+            let mut parts = line.split_whitespace();
+            let step = match (parts.next(), parts.next(), parts.next()) {
+                (Some(input), Some(score), None) => Ok(Step {
+                    input: parse_input(input)?,
+                    score: score.parse()?,
+                }),
+                _ => Err(custom_err(format!("Expected `<input> <score>`: {}", line))),
+            };
+            steps.push(step.map_err(|err| parse::on_line(err, ix, line))?);
+        }
+
+        Ok(Replay { steps })
+    }
+}
+
+//Runs a game without any window, logging every input and optionally keeping the frames
+pub struct Headless {
+    game: Game,
+    replay: Replay,
+    frames: Option<Vec<Grid<Tile>>>,
+}
+
+impl Headless {
+    pub fn new(game: Game) -> Headless {
+        Headless {
+            game,
+            replay: Replay::default(),
+            frames: None,
+        }
+    }
+
+    //Keeps a copy of the screen before the first and after every step
+    pub fn capture(mut self) -> Headless {
+        self.frames = Some(vec![self.game.tiles().clone()]);
+        self
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn frames(&self) -> &[Grid<Tile>] {
+        self.frames.as_deref().unwrap_or(&[])
+    }
+
+    pub fn step(&mut self, input: Input) -> AocResult<bool> {
+        let running = self.game.step(input)?;
+        self.replay.steps.push(Step {
+            input,
+            score: self.game.score(),
+        });
+        if let Some(frames) = &mut self.frames {
+            frames.push(self.game.tiles().clone());
+        }
+
+        Ok(running)
+    }
+
+    //Plays until the game is over or `max_steps` are taken, returns the score
+    pub fn run(&mut self, strategy: &mut dyn Strategy, max_steps: usize) -> AocResult<usize> {
+        for _ in 0..max_steps {
+            if self.game.halted() {
+                break;
+            }
+
+            let input = strategy.input(&self.game.observe()?);
+            if !self.step(input)? {
+                break;
+            }
+        }
+
+        Ok(self.game.score())
+    }
+
+    pub fn ascii(&self) -> String {
+        format!(
+            "Score: {}\n{}",
+            self.game.score(),
+            renderer().ascii(self.game.tiles())
+        )
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>, scale: u32) -> AocResult<()> {
+        renderer().scale(scale).save_png(self.game.tiles(), path)
+    }
+
+    pub fn save_gif(&self, path: impl AsRef<Path>, scale: u32, delay_ms: u16) -> AocResult<()> {
+        renderer()
+            .scale(scale)
+            .save_gif(self.frames(), delay_ms, path)
+    }
+
+    pub fn into_replay(self) -> Replay {
+        self.replay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day13::Greedy;
+
+    #[test]
+    fn test_record_replay() -> AocResult<()> {
+        let data: Data = parse_file(FileType::Input, 13, 1)?;
+        let mut headless = Headless::new(Game::create(data.clone(), true)?);
+        let score = headless.run(&mut Greedy, usize::MAX)?;
+        check_answer(FileType::Input, 13, 1, 2, score)?;
+        assert!(headless.game().halted());
+        assert_eq!(0, headless.game().blocks().count());
+
+        let replay: Replay = headless.into_replay().to_string().parse()?;
+        assert_eq!(score, replay.score());
+        assert_eq!(score, replay.play(data.clone())?);
+
+        let mut short = replay.clone();
+        short.steps.pop();
+        assert!(short.play(data.clone()).is_err());
+
+        let mut wrong = replay.clone();
+        wrong.steps[10].score += 1;
+        let err = wrong.play(data).err().unwrap();
+        assert!(err.to_string().contains("Step 10"));
+
+        assert!("N 0\nX 1\n".parse::<Replay>().is_err());
+        assert!("N\n".parse::<Replay>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_strategies() -> AocResult<()> {
+        let data: Data = parse_file(FileType::Input, 13, 1)?;
+        let outcomes = compare(&data, &mut [&mut Greedy, &mut Predictive], 100_000)?;
+        let report = report(&outcomes);
+        assert!(outcomes.iter().all(|o| o.cleared()), "{}", report);
+        for o in &outcomes {
+            check_answer(FileType::Input, 13, 1, 2, o.score)?;
+        }
+
+        //Waiting where the ball comes down saves most of the moves
+        assert_eq!("predictive", outcomes[0].name, "{}", report);
+        assert!(outcomes[0].moves * 2 < outcomes[1].moves, "{}", report);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_box_glyph() -> AocResult<()> {
+        let tiles = Grid::parse("####\n#*O#\n#_ #\n", |c| {
+            Some(match c {
+                '#' => Tile::Wall,
+                '*' => Tile::Block,
+                'O' => Tile::Ball,
+                '_' => Tile::HorizontalPaddle,
+                _ => Tile::Empty,
+            })
+        })?;
+        let text = tiles
+            .rows()
+            .enumerate()
+            .map(|(y, row)| {
+                (0..row.count())
+                    .map(|x| box_glyph(&tiles, (x, y)))
+                    .collect()
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(vec!["┌──┐", "│▒●│", "│▀ │"], text);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_frames() -> AocResult<()> {
+        let data: Data = parse_file(FileType::Input, 13, 1)?;
+        let mut headless = Headless::new(Game::create(data, true)?).capture();
+        headless.run(&mut Greedy, 5)?;
+        assert_eq!(6, headless.frames().len());
+        assert_ne!(headless.frames()[0], headless.frames()[5]);
+
+        let ascii = headless.ascii();
+        assert!(ascii.starts_with("Score: "));
+        assert_eq!(1, ascii.matches('O').count());
+
+        let root = std::env::temp_dir().join(format!("aoc19_breakout_{}", std::process::id()));
+        headless.save_png(root.join("frame.png"), 2)?;
+        headless.save_gif(root.join("game.gif"), 2, 50)?;
+        assert!(std::fs::read(root.join("game.gif"))?.starts_with(b"GIF89a"));
+        std::fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+}
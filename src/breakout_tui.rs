@@ -0,0 +1,211 @@
+use aoc19::breakout::{box_glyph, compare, palette, report, Headless, Predictive};
+use aoc19::days::day05::Data;
+use aoc19::days::day13::{Game, Greedy, Input, Strategy};
+use aoc19::helper::render::Styler;
+use aoc19::{custom_err, parse_file, AocResult, FileType};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use std::io::{self, Stdout, Write};
+use std::time::{Duration, Instant};
+
+const USAGE: &str =
+    "usage: breakout [--auto] [--tick <ms>] [--record <file>]\n       breakout --compare";
+
+const KEYS: &str = "←/→ move  a autopilot  space pause  +/- speed  r restart  q quit";
+
+const MIN_TICK: u64 = 5;
+const MAX_TICK: u64 = 1000;
+
+//Raw mode on an alternate screen, restored on drop even when a frame fails
+struct Term(Stdout);
+
+impl Term {
+    fn new() -> AocResult<Term> {
+        let mut out = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide, Clear(ClearType::All))?;
+        Ok(Term(out))
+    }
+}
+
+impl Drop for Term {
+    fn drop(&mut self) {
+        let _ = execute!(self.0, ResetColor, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct App {
+    data: Data,
+    session: Headless,
+    auto: bool,
+    paused: bool,
+    tick: u64,
+    //Last arrow key since the previous tick, terminals don't report key releases
+    next_input: Option<Input>,
+}
+
+impl App {
+    fn new(data: Data, auto: bool, tick: u64) -> AocResult<App> {
+        let session = Headless::new(Game::create(data.clone(), true)?);
+        Ok(App {
+            data,
+            session,
+            auto,
+            paused: false,
+            tick,
+            next_input: None,
+        })
+    }
+
+    fn restart(&mut self) -> AocResult<()> {
+        self.session = Headless::new(Game::create(self.data.clone(), true)?);
+        self.next_input = None;
+        Ok(())
+    }
+
+    //Returns false to quit
+    fn key(&mut self, key: KeyEvent) -> AocResult<bool> {
+        match key.code {
+            KeyCode::Left => self.next_input = Some(Input::Left),
+            KeyCode::Right => self.next_input = Some(Input::Right),
+            KeyCode::Down => self.next_input = Some(Input::Neutral),
+            KeyCode::Char('a') => self.auto = !self.auto,
+            KeyCode::Char(' ') | KeyCode::Char('p') => self.paused = !self.paused,
+            KeyCode::Char('+') | KeyCode::Char('=') => self.tick = (self.tick / 2).max(MIN_TICK),
+            KeyCode::Char('-') => self.tick = (self.tick * 2).min(MAX_TICK),
+            KeyCode::Char('r') => self.restart()?,
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            _ => {}
+        }
+
+        Ok(true)
+    }
+
+    fn update(&mut self) -> AocResult<()> {
+        if self.paused || self.session.game().halted() {
+            return Ok(());
+        }
+
+        let input = if self.auto {
+            Greedy.input(&self.session.game().observe()?)
+        } else {
+            self.next_input.take().unwrap_or(Input::Neutral)
+        };
+        self.session.step(input)?;
+        Ok(())
+    }
+
+    fn status(&self) -> String {
+        let game = self.session.game();
+        let blocks = game.blocks().count();
+        let state = if !game.halted() {
+            if self.paused {
+                "PAUSED"
+            } else {
+                ""
+            }
+        } else if blocks == 0 {
+            "YOU WIN - r to restart"
+        } else {
+            "GAME OVER - r to restart"
+        };
+
+        format!(
+            "Score {:>6}  Blocks {:>4}  {:<6}  {:>4}ms  {}",
+            game.score(),
+            blocks,
+            if self.auto { "auto" } else { "manual" },
+            self.tick,
+            state
+        )
+    }
+
+    fn draw(&self, out: &mut Stdout) -> AocResult<()> {
+        let tiles = self.session.game().tiles();
+        let palette = palette();
+        queue!(out, MoveTo(0, 0))?;
+
+        for (y, row) in tiles.rows().enumerate() {
+            let mut last = None;
+            for (x, tile) in row.enumerate() {
+                let [r, g, b] = palette.style(tile).color;
+                if last != Some((r, g, b)) {
+                    queue!(out, SetForegroundColor(Color::Rgb { r, g, b }))?;
+                    last = Some((r, g, b));
+                }
+                queue!(out, Print(box_glyph(tiles, (x, y))))?;
+            }
+            queue!(out, ResetColor, Print("\r\n"))?;
+        }
+
+        queue!(
+            out,
+            Clear(ClearType::UntilNewLine),
+            Print(self.status()),
+            Print("\r\n"),
+            Print(KEYS)
+        )?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+fn run(app: &mut App, term: &mut Term) -> AocResult<()> {
+    let mut next_tick = Instant::now();
+    loop {
+        app.draw(&mut term.0)?;
+
+        let timeout = next_tick.saturating_duration_since(Instant::now());
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Release && !app.key(key)? {
+                    return Ok(());
+                }
+            }
+            continue;
+        }
+
+        app.update()?;
+        next_tick = Instant::now() + Duration::from_millis(app.tick);
+    }
+}
+
+fn main() -> AocResult<()> {
+    let mut auto = false;
+    let mut tick = 50;
+    let mut record = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| custom_err(USAGE));
+        match arg.as_str() {
+            "--auto" => auto = true,
+            "--compare" => {
+                let data: Data = parse_file(FileType::Input, 13, 1)?;
+                let outcomes = compare(&data, &mut [&mut Greedy, &mut Predictive], 1_000_000)?;
+                print!("{}", report(&outcomes));
+                return Ok(());
+            }
+            "--tick" => tick = value()?.parse::<u64>()?.clamp(MIN_TICK, MAX_TICK),
+            "--record" => record = Some(value()?),
+            _ => return Err(custom_err(USAGE)),
+        }
+    }
+
+    let data: Data = parse_file(FileType::Input, 13, 1)?;
+    let mut app = App::new(data, auto, tick)?;
+
+    let res = Term::new().and_then(|mut term| run(&mut app, &mut term));
+
+    if let Some(path) = record {
+        app.session.replay().save(&path)?;
+        println!("Replay saved to {}", path);
+    }
+    println!("Score: {}", app.session.game().score());
+
+    res
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use crate::solution::Solution;
 use crate::*;
 
 fn load_masses() -> AocResult<Vec<u64>> {
@@ -27,6 +28,28 @@ fn calc_total_fuel(mass: u64) -> u64 {
     }
 }
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: usize = 1;
+    type Data = Vec<u64>;
+    type Answer1 = i64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> AocResult<Vec<u64>> {
+        let v: ParseLineVec<u64> = input.parse()?;
+        Ok(v.0)
+    }
+
+    fn part1(data: &Vec<u64>) -> AocResult<i64> {
+        Ok(data.iter().map(|&m| calc_fuel(m)).sum())
+    }
+
+    fn part2(data: &Vec<u64>) -> AocResult<u64> {
+        Ok(data.iter().map(|&m| calc_total_fuel(m)).sum())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,8 +66,7 @@ mod tests {
 
         let s: i64 = m.iter().map(|&m| calc_fuel(m)).sum();
 
-        assert_eq!(s, 3_297_896);
-        Ok(())
+        check_answer(FileType::Input, 1, 1, 1, s)
     }
 
     #[test]
@@ -53,7 +75,6 @@ mod tests {
 
         let s: u64 = m.iter().map(|&m| calc_total_fuel(m)).sum();
 
-        assert_eq!(s, 4_943_969);
-        Ok(())
+        check_answer(FileType::Input, 1, 1, 2, s)
     }
 }
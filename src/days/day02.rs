@@ -1,9 +1,49 @@
 #![allow(dead_code)]
 
+use crate::days::day05::{Context, Data};
+use crate::intcode::sym::{Solver, Target};
+use crate::solution::Solution;
+use crate::*;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: usize = 2;
+    type Data = Data;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<isize> {
+        //1202 program alarm
+        let mut data = data.clone();
+        data.0[1] = 12;
+        data.0[2] = 2;
+
+        let mut ctx = Context::from_data(data, &[]);
+        ctx.resume()?;
+        Ok(ctx.read(0))
+    }
+
+    fn part2(data: &Data) -> AocResult<isize> {
+        let solution = Solver::new(data.clone())
+            .var_mem(1, 0..=99)
+            .var_mem(2, 0..=99)
+            .solve(Target::Mem(0), 19_690_720)?
+            .ok_or_else(|| custom_err("No noun and verb found"))?;
+
+        Ok(100 * solution[0] + solution[1])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
     use crate::days::day05::*;
+    use crate::intcode::sym::{Solver, Target};
 
     #[test]
     fn test1() -> AocResult<()> {
@@ -27,9 +67,7 @@ mod tests {
         let mut ctx = Context::from_data(data, &[]);
         ctx.resume()?;
 
-        assert_eq!(3_306_701, ctx.read(0));
-
-        Ok(())
+        check_answer(FileType::Input, 2, 1, 1, ctx.read(0))
     }
 
     #[test]
@@ -45,12 +83,23 @@ mod tests {
                 let mut ctx = Context::from_data(data, &[]);
 
                 if ctx.resume().is_ok() && ctx.read(0) == OUTPUT {
-                    assert_eq!(7621, 100 * noun + verb);
-                    return Ok(());
+                    return check_answer(FileType::Input, 2, 1, 2, 100 * noun + verb);
                 }
             }
         }
 
         unreachable!("All combs used");
     }
+
+    #[test]
+    fn part2_sym() -> AocResult<()> {
+        let data: Data = parse_file(FileType::Input, 2, 1)?;
+        let solution = Solver::new(data)
+            .var_mem(1, 0..=99)
+            .var_mem(2, 0..=99)
+            .solve(Target::Mem(0), 19_690_720)?
+            .unwrap();
+
+        check_answer(FileType::Input, 2, 1, 2, 100 * solution[0] + solution[1])
+    }
 }
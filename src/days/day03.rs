@@ -1,47 +1,48 @@
 #![allow(dead_code)]
+use crate::helper::dir::Dir;
+use crate::helper::vec::Vec2;
+use crate::parse::ParseCtx;
+use crate::solution::Solution;
 use crate::*;
 use std::collections::HashMap;
 use std::str::FromStr;
 
 #[derive(Debug, Clone)]
-pub enum Dir {
-    Up(usize),
-    Down(usize),
-    Right(usize),
-    Left(usize),
-}
+pub struct Move(pub Dir, pub usize);
 
 #[derive(Debug, Clone)]
-pub struct Wire(pub Vec<Dir>);
+pub struct Wire(pub Vec<Move>);
 
 impl FromStr for Wire {
     type Err = AocErr;
     fn from_str(s: &str) -> AocResult<Self> {
-        let v: Result<Vec<_>, _> = s.split(',').map(|s| s.parse()).collect();
+        let ctx = ParseCtx::new(s);
+        let v: Result<Vec<_>, _> = s.split(',').map(|s| Move::parse(&ctx, s)).collect();
 
         Ok(Wire(v?))
     }
 }
 
-impl FromStr for Dir {
-    type Err = AocErr;
+impl Move {
+    fn parse(ctx: &ParseCtx, s: &str) -> AocResult<Self> {
+        let dir = s.chars().next().ok_or_else(|| ctx.error(s, "Empty dir"))?;
+        let (dir_str, num) = s.split_at(dir.len_utf8());
+        let num = ctx.parse(num)?;
 
-    fn from_str(s: &str) -> AocResult<Self> {
-        if s.is_empty() {
-            return Err(AocErr::Custom("Empty dir".to_string()));
-        }
+        let dir = Some(dir)
+            .filter(|c| "UDRL".contains(*c))
+            .and_then(Dir::from_char)
+            .ok_or_else(|| ctx.error(dir_str, "Invalid dir, expected U, D, R or L"))?;
 
-        let (dir, num) = s.split_at(1);
-        let dir = dir.chars().next().unwrap();
-        let num = num.parse()?;
+        Ok(Move(dir, num))
+    }
+}
 
-        Ok(match dir {
-            'U' => Dir::Up(num),
-            'D' => Dir::Down(num),
-            'R' => Dir::Right(num),
-            'L' => Dir::Left(num),
-            _ => return Err(AocErr::Custom("Invalid dir".to_string())),
-        })
+impl FromStr for Move {
+    type Err = AocErr;
+
+    fn from_str(s: &str) -> AocResult<Self> {
+        Move::parse(&ParseCtx::new(s), s)
     }
 }
 
@@ -68,22 +69,9 @@ impl FromStr for Data {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Point2(pub isize, pub isize);
-
-impl Point2 {
-    fn dist(&self, other: &Point2) -> usize {
-        ((self.0 - other.0).abs() + (self.1 - other.1).abs()) as usize
-    }
-
-    fn add(&self, x: isize, y: isize) -> Point2 {
-        Point2(self.0 + x, self.1 + y)
-    }
-}
-
 #[derive(Debug)]
 struct Circuit {
-    board: HashMap<Point2, u8>,
+    board: HashMap<Vec2, u8>,
     wire1: Wire,
     wire2: Wire,
 }
@@ -102,19 +90,19 @@ impl Circuit {
         circ
     }
 
-    fn center(&self) -> Point2 {
-        Point2(0, 0)
+    fn center(&self) -> Vec2 {
+        Vec2::ZERO
     }
 
-    fn get_mut(&mut self, p: Point2) -> &mut u8 {
+    fn get_mut(&mut self, p: Vec2) -> &mut u8 {
         self.board.entry(p).or_insert(0)
     }
 
-    fn get(&self, p: &Point2) -> &u8 {
-        self.board.get(&p).unwrap()
+    fn get(&self, p: &Vec2) -> &u8 {
+        self.board.get(p).unwrap()
     }
 
-    fn set_point(&mut self, pt: Point2, wire_id: u8) {
+    fn set_point(&mut self, pt: Vec2, wire_id: u8) {
         let p = self.get_mut(pt);
 
         let new = match *p {
@@ -128,43 +116,33 @@ impl Circuit {
 
     fn add_wire(&mut self, wire: &Wire, wire_id: u8) {
         let mut p = self.center();
-        for dir in &wire.0 {
-            let (x, y, steps) = match dir {
-                Dir::Down(n) => (0, 1, *n),
-                Dir::Up(n) => (0, -1, *n),
-                Dir::Left(n) => (-1, 0, *n),
-                Dir::Right(n) => (1, 0, *n),
-            };
+        for mv in &wire.0 {
+            let (off, steps) = (Vec2::from(mv.0), mv.1);
 
             for _i in 0..steps {
-                p = p.add(x, y);
+                p += off;
                 self.set_point(p, wire_id);
             }
         }
     }
 
-    fn intersections(&self) -> impl Iterator<Item = Point2> + '_ {
+    fn intersections(&self) -> impl Iterator<Item = Vec2> + '_ {
         self.board
             .iter()
             .filter_map(|(&k, &v)| if v == 255 { Some(k) } else { None })
     }
 
-    fn step_ints(&self, wire: &Wire) -> HashMap<Point2, usize> {
+    fn step_ints(&self, wire: &Wire) -> HashMap<Vec2, usize> {
         let mut shortest = HashMap::new();
         let mut step = 0;
 
         let mut p = self.center();
-        for dir in &wire.0 {
-            let (x, y, steps) = match dir {
-                Dir::Down(n) => (0, 1, *n),
-                Dir::Up(n) => (0, -1, *n),
-                Dir::Left(n) => (-1, 0, *n),
-                Dir::Right(n) => (1, 0, *n),
-            };
+        for mv in &wire.0 {
+            let (off, steps) = (Vec2::from(mv.0), mv.1);
 
             for _i in 0..steps {
                 step += 1;
-                p = p.add(x, y);
+                p += off;
 
                 if *self.get(&p) == 255 {
                     shortest.entry(p).or_insert(step);
@@ -175,23 +153,44 @@ impl Circuit {
         shortest
     }
 
-    fn find_shortest_int_steps(&self) -> usize {
+    fn find_shortest_int_steps(&self) -> AocResult<usize> {
         let shortest1 = self.step_ints(&self.wire1);
         let shortest2 = self.step_ints(&self.wire2);
 
         shortest1
             .iter()
-            .map(|(pt, steps)| steps + shortest2[pt])
+            .filter_map(|(pt, steps)| Some(steps + shortest2.get(pt)?))
             .min()
-            .unwrap()
+            .ok_or_else(|| custom_err("Wires don't cross"))
     }
 
-    fn shortest_dist_intersection(&self) -> usize {
+    fn shortest_dist_intersection(&self) -> AocResult<usize> {
         let center = self.center();
         self.intersections()
-            .map(|int| int.dist(&center))
+            .map(|int| int.manhattan_dist(center))
             .min()
-            .unwrap()
+            .ok_or_else(|| custom_err("Wires don't cross"))
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: usize = 3;
+    type Data = Data;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<usize> {
+        Circuit::from_data(data).shortest_dist_intersection()
+    }
+
+    fn part2(data: &Data) -> AocResult<usize> {
+        Circuit::from_data(data).find_shortest_int_steps()
     }
 }
 
@@ -204,10 +203,8 @@ mod tests {
         let data: Data = parse_file(FileType::Input, 3, 1)?;
         let circuit = Circuit::from_data(&data);
 
-        assert_eq!(circuit.shortest_dist_intersection(), 1626);
-        assert_eq!(circuit.find_shortest_int_steps(), 27330);
-
-        Ok(())
+        check_answer(FileType::Input, 3, 1, 1, circuit.shortest_dist_intersection()?)?;
+        check_answer(FileType::Input, 3, 1, 2, circuit.find_shortest_int_steps()?)
     }
 
     #[test]
@@ -215,8 +212,8 @@ mod tests {
         let data: Data = parse_file(FileType::Example, 3, 1)?;
         let circuit = Circuit::from_data(&data);
 
-        assert_eq!(circuit.shortest_dist_intersection(), 135);
-        assert_eq!(circuit.find_shortest_int_steps(), 410);
+        assert_eq!(circuit.shortest_dist_intersection()?, 135);
+        assert_eq!(circuit.find_shortest_int_steps()?, 410);
 
         Ok(())
     }
@@ -226,9 +223,27 @@ mod tests {
         let data: Data = parse_file(FileType::Example, 3, 2)?;
         let circuit = Circuit::from_data(&data);
 
-        assert_eq!(circuit.shortest_dist_intersection(), 159);
-        assert_eq!(circuit.find_shortest_int_steps(), 610);
+        assert_eq!(circuit.shortest_dist_intersection()?, 159);
+        assert_eq!(circuit.find_shortest_int_steps()?, 610);
 
         Ok(())
     }
+
+    #[test]
+    fn test_no_crossing() -> AocResult<()> {
+        let data: Data = "R8\nU5".parse()?;
+        assert!(Day03::part1(&data).is_err());
+        assert!(Day03::part2(&data).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = "R8,U5\nU7,X6".parse::<Data>().unwrap_err();
+        assert_eq!(
+            "<input>:2:4: Invalid dir, expected U, D, R or L at `X`",
+            err.to_string()
+        );
+    }
 }
@@ -1,5 +1,8 @@
 #![allow(dead_code)]
 
+use crate::solution::Solution;
+use crate::*;
+
 fn digits_iter(num: usize) -> impl Iterator<Item = u8> + 'static {
     let mut num = num;
     [100_000, 10_000, 1_000, 100, 10, 1]
@@ -12,7 +15,7 @@ fn digits_iter(num: usize) -> impl Iterator<Item = u8> + 'static {
 }
 
 fn is_valid_pw(pw: usize) -> bool {
-    if pw < 100_000 || pw > 1_000_000 {
+    if !(100_000..=1_000_000).contains(&pw) {
         return false;
     }
 
@@ -35,7 +38,7 @@ fn is_valid_pw(pw: usize) -> bool {
 }
 
 fn is_valid_pw2(pw: usize) -> bool {
-    if pw < 100_000 || pw > 1_000_000 {
+    if !(100_000..=1_000_000).contains(&pw) {
         return false;
     }
 
@@ -67,10 +70,38 @@ fn is_valid_pw2(pw: usize) -> bool {
     same
 }
 
+fn parse_range(s: &str) -> AocResult<(usize, usize)> {
+    let ix = s.find('-').ok_or_else(|| custom_err("Range without -"))?;
+    let (from, to) = s.split_at(ix);
+
+    Ok((from.trim().parse()?, to[1..].trim().parse()?))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: usize = 4;
+    type Data = (usize, usize);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AocResult<(usize, usize)> {
+        parse_range(input)
+    }
+
+    fn part1(&(from, to): &(usize, usize)) -> AocResult<usize> {
+        Ok((from..=to).filter(|&pw| is_valid_pw(pw)).count())
+    }
+
+    fn part2(&(from, to): &(usize, usize)) -> AocResult<usize> {
+        Ok((from..=to).filter(|&pw| is_valid_pw2(pw)).count())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::*;
     use super::*;
+    use std::fs;
 
     #[test]
     fn part1() -> AocResult<()> {
@@ -78,10 +109,8 @@ mod tests {
         assert!(!is_valid_pw(223_450));
         assert!(!is_valid_pw(123_789));
 
-        let n = (234_208..765_869).filter(|&pw| is_valid_pw(pw)).count();
-
-        assert_eq!(n, 1246);
-        Ok(())
+        let data = Day04::parse(&fs::read_to_string(file_path(FileType::Input, 4, 1))?)?;
+        check_answer(FileType::Input, 4, 1, 1, Day04::part1(&data)?)
     }
 
     #[test]
@@ -90,10 +119,7 @@ mod tests {
         assert!(!is_valid_pw2(123_444));
         assert!(is_valid_pw2(111_122));
 
-        let n = (234_208..765_869).filter(|&pw| is_valid_pw2(pw)).count();
-
-        assert_eq!(n, 814);
-
-        Ok(())
+        let data = Day04::parse(&fs::read_to_string(file_path(FileType::Input, 4, 1))?)?;
+        check_answer(FileType::Input, 4, 1, 2, Day04::part2(&data)?)
     }
 }
@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
+use crate::solution::Solution;
 use crate::*;
+use crate::intcode::format::parse_text;
 use std::collections::VecDeque;
 use std::iter::once;
 use std::str::FromStr;
@@ -13,23 +15,19 @@ pub struct Data(pub Vec<isize>);
 impl FromStr for Data {
     type Err = AocErr;
     fn from_str(s: &str) -> AocResult<Data> {
-        let mut v = Vec::new();
-        for s in s.split(',') {
-            v.push(s.parse()?);
-        }
-        Ok(Data(v))
+        Ok(Data(parse_text(s)?))
     }
 }
 
 #[derive(Debug)]
-enum Value {
+pub(crate) enum Value {
     Position(usize),
     Immediate(isize),
     Relative(isize),
 }
 
 #[derive(Debug)]
-enum Opcode {
+pub(crate) enum Opcode {
     Add(Value, Value, Value),
     Mul(Value, Value, Value),
     In(Value),
@@ -42,6 +40,7 @@ enum Opcode {
     SetBase(Value),
 }
 
+#[derive(Clone)]
 pub struct Context {
     data: Vec<isize>,
     input: VecDeque<isize>,
@@ -51,16 +50,16 @@ pub struct Context {
     base: isize,
 }
 
-fn decode_val(value: isize, mode: u8) -> Value {
-    match mode {
+fn decode_val(value: isize, mode: u8) -> AocResult<Value> {
+    Ok(match mode {
         0 => Value::Position(value as usize),
         1 => Value::Immediate(value),
         2 => Value::Relative(value),
-        _ => unreachable!(),
-    }
+        _ => return Err(custom_err(format!("Invalid mode {}", mode))),
+    })
 }
 
-fn decode(data: &[isize]) -> AocResult<(Opcode, usize)> {
+pub(crate) fn decode(data: &[isize]) -> AocResult<(Opcode, usize)> {
     let mut op = data[0];
 
     let third_mode = (op / 10_000) as u8;
@@ -75,53 +74,53 @@ fn decode(data: &[isize]) -> AocResult<(Opcode, usize)> {
     Ok(match op {
         1 => (
             Opcode::Add(
-                decode_val(data[1], first_mode),
-                decode_val(data[2], second_mode),
-                decode_val(data[3], third_mode),
+                decode_val(data[1], first_mode)?,
+                decode_val(data[2], second_mode)?,
+                decode_val(data[3], third_mode)?,
             ),
             4,
         ),
         2 => (
             Opcode::Mul(
-                decode_val(data[1], first_mode),
-                decode_val(data[2], second_mode),
-                decode_val(data[3], third_mode),
+                decode_val(data[1], first_mode)?,
+                decode_val(data[2], second_mode)?,
+                decode_val(data[3], third_mode)?,
             ),
             4,
         ),
-        3 => (Opcode::In(decode_val(data[1], first_mode)), 2),
-        4 => (Opcode::Out(decode_val(data[1], first_mode)), 2),
+        3 => (Opcode::In(decode_val(data[1], first_mode)?), 2),
+        4 => (Opcode::Out(decode_val(data[1], first_mode)?), 2),
         5 => (
             Opcode::JumpTrue(
-                decode_val(data[1], first_mode),
-                decode_val(data[2], second_mode),
+                decode_val(data[1], first_mode)?,
+                decode_val(data[2], second_mode)?,
             ),
             3,
         ),
         6 => (
             Opcode::JumpFalse(
-                decode_val(data[1], first_mode),
-                decode_val(data[2], second_mode),
+                decode_val(data[1], first_mode)?,
+                decode_val(data[2], second_mode)?,
             ),
             3,
         ),
         7 => (
             Opcode::CmpLt(
-                decode_val(data[1], first_mode),
-                decode_val(data[2], second_mode),
-                decode_val(data[3], third_mode),
+                decode_val(data[1], first_mode)?,
+                decode_val(data[2], second_mode)?,
+                decode_val(data[3], third_mode)?,
             ),
             4,
         ),
         8 => (
             Opcode::CmpEq(
-                decode_val(data[1], first_mode),
-                decode_val(data[2], second_mode),
-                decode_val(data[3], third_mode),
+                decode_val(data[1], first_mode)?,
+                decode_val(data[2], second_mode)?,
+                decode_val(data[3], third_mode)?,
             ),
             4,
         ),
-        9 => (Opcode::SetBase(decode_val(data[1], first_mode)), 2),
+        9 => (Opcode::SetBase(decode_val(data[1], first_mode)?), 2),
         99 => (Opcode::Halt, 1),
         _ => return Err(AocErr::Custom(format!("Invalid Opcode {}", data[0]))),
     })
@@ -141,6 +140,24 @@ impl Context {
         }
     }
 
+    //Restores a machine stopped mid-run, e.g. from a saved image
+    pub fn from_state(
+        data: Data,
+        pc: usize,
+        base: isize,
+        halted: bool,
+        inputs: &[isize],
+        outputs: Vec<isize>,
+    ) -> Context {
+        Context {
+            pc,
+            base,
+            halted,
+            output: outputs,
+            ..Self::from_data(data, inputs)
+        }
+    }
+
     pub fn from_data_fill_up(mut data: Data, inputs: &[isize]) -> Context {
         let fill = 16_000 - data.0.len();
         data.0.extend(once(0).cycle().take(fill));
@@ -164,6 +181,18 @@ impl Context {
         self.halted
     }
 
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn base(&self) -> isize {
+        self.base
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = isize> + '_ {
+        self.input.iter().cloned()
+    }
+
     pub fn output(&self) -> Option<isize> {
         self.output.last().cloned()
     }
@@ -177,12 +206,27 @@ impl Context {
         self.output.pop()
     }
 
+    //Removes and returns the oldest `n` outputs
+    pub fn take_outputs(&mut self, n: usize) -> Vec<isize> {
+        self.output.drain(..n.min(self.output.len())).collect()
+    }
+
     pub fn push_input(&mut self, input: isize) {
         self.input.push_back(input);
     }
 
     pub fn input_len(&self) -> usize {
-        return self.input.len()
+        self.input.len()
+    }
+
+    //Stopped at an input instruction with nothing queued
+    pub fn needs_input(&self) -> bool {
+        !self.halted
+            && self.input.is_empty()
+            && matches!(
+                self.data.get(self.pc..).map(decode),
+                Some(Ok((Opcode::In(_), _)))
+            )
     }
 
     fn bool_to_num(b: bool) -> isize {
@@ -238,10 +282,14 @@ impl Context {
                     self.write_val(&c, value);
                 }
                 Opcode::In(a) => {
-                    let value = self
-                        .input
-                        .pop_front()
-                        .ok_or_else(|| custom_err("Not enough inputs"))?;
+                    let value = match self.input.pop_front() {
+                        Some(value) => value,
+                        None => {
+                            //Rewind so the instruction is retried once input arrives
+                            self.pc -= ln;
+                            return Err(custom_err("Not enough inputs"));
+                        }
+                    };
                     self.write_val(&a, value);
                 }
                 Opcode::Out(a) => {
@@ -284,6 +332,27 @@ fn run(data: Data, input: isize) -> AocResult<isize> {
     ctx.exec()
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: usize = 5;
+    type Data = Data;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<isize> {
+        run(data.clone(), 1)
+    }
+
+    fn part2(data: &Data) -> AocResult<isize> {
+        run(data.clone(), 5)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,16 +371,12 @@ mod tests {
     #[test]
     fn part1() -> AocResult<()> {
         let data: Data = parse_file(FileType::Input, 5, 1)?;
-        assert_eq!(13_547_311, run(data, 1)?);
-
-        Ok(())
+        check_answer(FileType::Input, 5, 1, 1, run(data, 1)?)
     }
 
     #[test]
     fn part2() -> AocResult<()> {
         let data: Data = parse_file(FileType::Input, 5, 1)?;
-        assert_eq!(236_453, run(data, 5)?);
-
-        Ok(())
+        check_answer(FileType::Input, 5, 1, 2, run(data, 5)?)
     }
 }
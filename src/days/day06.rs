@@ -1,24 +1,31 @@
 #![allow(dead_code)]
 
+use crate::helper::search;
+use crate::parse::Cursor;
+use crate::solution::Solution;
 use crate::*;
 use id_tree::*;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct Orbits(String, String);
+#[derive(Clone)]
 pub struct Data(Vec<Orbits>);
 
+fn orbit(c: &mut Cursor) -> AocResult<Orbits> {
+    let center = c.ident()?;
+    c.tag(")")?;
+    let satellite = c.ident()?;
+
+    Ok(Orbits(center.to_string(), satellite.to_string()))
+}
+
 impl FromStr for Orbits {
     type Err = AocErr;
 
     fn from_str(s: &str) -> AocResult<Orbits> {
-        let ix = s
-            .find(')')
-            .ok_or_else(|| custom_err("Orbit relation invalid"))?;
-        let (s1, s2) = s.split_at(ix);
-
-        Ok(Orbits(s1.to_string(), s2[1..].to_string()))
+        Cursor::complete(s, orbit)
     }
 }
 
@@ -26,8 +33,7 @@ impl FromStr for Data {
     type Err = AocErr;
 
     fn from_str(s: &str) -> AocResult<Self> {
-        let v: ParseLineVec<Orbits> = s.parse()?;
-        Ok(Data(v.0))
+        Ok(Data(Cursor::new(s).lines(orbit)?))
     }
 }
 
@@ -74,7 +80,7 @@ fn calc_checksum(data: Data) -> AocResult<usize> {
 
     let root_id = tree.root_node_id().unwrap();
     let sum = tree
-        .traverse_pre_order(&root_id)
+        .traverse_pre_order(root_id)
         .unwrap()
         .map(|n| n.data().1)
         .sum();
@@ -83,46 +89,42 @@ fn calc_checksum(data: Data) -> AocResult<usize> {
 }
 
 fn calc_orbit_moves(data: Data) -> AocResult<usize> {
-    let tree = build_tree(data);
-    let root_id = tree.root_node_id().unwrap();
+    let mut graph = HashMap::<&str, Vec<&str>>::new();
+    for Orbits(center, satellite) in data.0.iter() {
+        graph.entry(center).or_default().push(satellite);
+        graph.entry(satellite).or_default().push(center);
+    }
 
-    let find_node = |s: String| {
-        tree.traverse_pre_order(&root_id)
-            .unwrap()
-            .find(|n| n.data().0 == s)
-            .unwrap()
-    };
-
-    let get_parent = |n: &Node<(String, usize)>| tree.get(n.parent().unwrap()).unwrap();
-
-    let mut left = find_node("YOU".to_string());
-    let mut right = find_node("SAN".to_string());
-    let mut steps = 0;
-
-    //Find least common ancestor
-    while left != right {
-        let left_level = left.data().1;
-        let right_level = right.data().1;
-
-        use std::cmp::Ordering::*;
-        match left_level.cmp(&right_level) {
-            Less => {
-                right = get_parent(right);
-                steps += 1;
-            }
-            Greater => {
-                left = get_parent(left);
-                steps += 1;
-            }
-            Equal => {
-                right = get_parent(right);
-                left = get_parent(left);
-                steps += 2;
-            }
-        }
+    let path = search::bfs(
+        "YOU",
+        |n| graph.get(n).cloned().unwrap_or_default(),
+        |&n| n == "SAN",
+    )
+    .ok_or_else(|| custom_err("No path from YOU to SAN"))?;
+
+    //Transfers between the objects YOU and SAN orbit
+    Ok(path.len().saturating_sub(3))
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: usize = 6;
+    type Data = Data;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<usize> {
+        calc_checksum(data.clone())
     }
 
-    Ok(steps - 2)
+    fn part2(data: &Data) -> AocResult<usize> {
+        calc_orbit_moves(data.clone())
+    }
 }
 
 #[cfg(test)]
@@ -143,9 +145,7 @@ mod tests {
         assert_eq!(calc_checksum(data)?, 42);
 
         let data: Data = parse_file(FileType::Input, 6, 1)?;
-        assert_eq!(calc_checksum(data)?, 139_597);
-
-        Ok(())
+        check_answer(FileType::Input, 6, 1, 1, calc_checksum(data)?)
     }
 
     #[test]
@@ -154,8 +154,6 @@ mod tests {
         assert_eq!(calc_orbit_moves(data)?, 4);
 
         let data: Data = parse_file(FileType::Input, 6, 1)?;
-        assert_eq!(calc_orbit_moves(data)?, 286);
-
-        Ok(())
+        check_answer(FileType::Input, 6, 1, 2, calc_orbit_moves(data)?)
     }
 }
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use super::day05::*;
+use crate::solution::Solution;
 use crate::*;
 use fallible_iterator::{convert, FallibleIterator};
 use itertools::Itertools;
@@ -57,6 +58,27 @@ fn find_max_signal(data: Data, input: isize) -> AocResult<isize> {
     )
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: usize = 7;
+    type Data = Data;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<isize> {
+        find_max_signal(data.clone(), 0)
+    }
+
+    fn part2(data: &Data) -> AocResult<isize> {
+        find_max_signal2(data.clone(), 0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,9 +104,7 @@ mod tests {
         assert_eq!(find_max_signal(data, 0)?, 43210);
 
         let data: Data = parse_file(FileType::Input, 7, 1)?;
-        assert_eq!(find_max_signal(data, 0)?, 338_603);
-
-        Ok(())
+        check_answer(FileType::Input, 7, 1, 1, find_max_signal(data, 0)?)
     }
 
     #[test]
@@ -95,8 +115,6 @@ mod tests {
         assert_eq!(find_max_signal2(data, 0)?, 139_629_729);
 
         let data: Data = parse_file(FileType::Input, 7, 1)?;
-        assert_eq!(find_max_signal2(data, 0)?, 63_103_596);
-
-        Ok(())
+        check_answer(FileType::Input, 7, 1, 2, find_max_signal2(data, 0)?)
     }
 }
@@ -1,7 +1,13 @@
 #![allow(dead_code)]
 
+use crate::helper::grid::Grid;
+use crate::helper::ocr;
+use crate::helper::render::{Renderer, Style};
+use crate::parse::ParseCtx;
+use crate::solution::Solution;
 use crate::*;
 use fallible_iterator::{convert, FallibleIterator};
+use std::path::Path;
 use std::str::FromStr;
 
 struct Data(Vec<u8>);
@@ -21,13 +27,31 @@ impl FromStr for Data {
     }
 }
 
-struct Image {
+pub struct Image {
     data: Vec<u8>,
     width: usize,
     height: usize,
 }
 
-struct Layer<'a> {
+//Colors used for digits in png files, 0 black, 1 white, 2 transparent grey
+const COLORS: [[u8; 3]; 10] = [
+    [0, 0, 0],
+    [255, 255, 255],
+    [128, 128, 128],
+    [255, 0, 0],
+    [0, 255, 0],
+    [0, 0, 255],
+    [255, 255, 0],
+    [0, 255, 255],
+    [255, 0, 255],
+    [255, 128, 0],
+];
+
+fn digit_style(&d: &u8) -> Style {
+    Style::new(COLORS[d as usize], (b'0' + d) as char)
+}
+
+pub struct Layer<'a> {
     img: &'a Image,
     ix: usize,
 }
@@ -44,6 +68,23 @@ impl<'a> Layer<'a> {
         (0..self.img.layer_size()).map(move |i| self.img.data[self.ix + i])
     }
 
+    //Count of each digit
+    pub fn histogram(&self) -> [usize; 10] {
+        let mut h = [0; 10];
+        for d in self.digits_iter() {
+            h[d as usize] += 1;
+        }
+
+        h
+    }
+
+    pub fn to_grid(&self) -> Grid<u8> {
+        let (img, ix) = (self.img, self.ix);
+        Grid::from_fn(img.width, img.height, |(x, y)| {
+            img.data[ix + y * img.width + x]
+        })
+    }
+
     fn print(&self) {
         for row in self.rows_iter() {
             for &digit in row {
@@ -65,68 +106,221 @@ impl Image {
         }
     }
 
+    //Strict decoding, only digits (and a final newline) and complete layers
+    pub fn decode(s: &str, width: usize, height: usize) -> AocResult<Image> {
+        let ctx = ParseCtx::new(s);
+        let digits = s.trim_end_matches(['\n', '\r']);
+        let data = digits
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| ctx.error(&digits[i..i + c.len_utf8()], "Invalid digit"))
+            })
+            .collect::<AocResult<Vec<_>>>()?;
+
+        let layer_size = width * height;
+        if layer_size == 0 {
+            return Err(custom_err(format!("Invalid size {}x{}", width, height)));
+        }
+        if data.is_empty() {
+            return Err(custom_err("No layers"));
+        }
+        if data.len() % layer_size != 0 {
+            let rest = data.len() % layer_size;
+            return Err(ctx.error(
+                &digits[digits.len() - rest..],
+                format!("Partial layer, {} of {} digits", rest, layer_size),
+            ));
+        }
+
+        Ok(Image {
+            data,
+            width,
+            height,
+        })
+    }
+
+    //Stacks layers top first, all layers must have the same size
+    pub fn from_layers(layers: &[Grid<u8>]) -> AocResult<Image> {
+        let first = layers.first().ok_or_else(|| custom_err("No layers"))?;
+        let (width, height) = (first.width(), first.height());
+        let mut data = Vec::with_capacity(width * height * layers.len());
+
+        for (i, layer) in layers.iter().enumerate() {
+            if (layer.width(), layer.height()) != (width, height) {
+                return Err(custom_err(format!(
+                    "Layer {} is {}x{}, expected {}x{}",
+                    i,
+                    layer.width(),
+                    layer.height(),
+                    width,
+                    height
+                )));
+            }
+            if let Some(&d) = layer.values().find(|&&d| d > 9) {
+                return Err(custom_err(format!("Invalid digit {} in layer {}", d, i)));
+            }
+
+            data.extend(layer.values());
+        }
+
+        Ok(Image {
+            data,
+            width,
+            height,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        self.data.iter().map(|&d| (b'0' + d) as char).collect()
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
     fn layer_size(&self) -> usize {
         self.width * self.height
     }
 
-    fn layers_len(&self) -> usize {
-        (self.data.len() / self.layer_size())
+    pub fn layers_len(&self) -> usize {
+        self.data.len() / self.layer_size()
     }
 
-    fn layer(&self, ix: usize) -> Option<Layer<'_>> {
-        let ix = ix * self.width * self.height;
-        if self.layers_len() == 0 {
-            None
+    pub fn layer(&self, ix: usize) -> Option<Layer<'_>> {
+        if ix < self.layers_len() {
+            Some(Layer {
+                img: self,
+                ix: ix * self.layer_size(),
+            })
         } else {
-            Some(Layer { img: self, ix })
+            None
         }
     }
 
-    fn layers_iter(&self) -> impl DoubleEndedIterator<Item = Layer<'_>> + '_ {
+    pub fn layers_iter(&self) -> impl DoubleEndedIterator<Item = Layer<'_>> + '_ {
         (0..self.layers_len()).map(move |ix| self.layer(ix).unwrap())
     }
 
+    pub fn histograms(&self) -> Vec<[usize; 10]> {
+        self.layers_iter().map(|l| l.histogram()).collect()
+    }
+
     fn lowest_layer(&self) -> usize {
-        let min_layer = self
-            .layers_iter()
-            .min_by_key(|l| l.digits_iter().filter(|&d| d == 0).count())
-            .unwrap();
+        let h = self.histograms().into_iter().min_by_key(|h| h[0]).unwrap();
+        h[1] * h[2]
+    }
 
-        let ones = min_layer.digits_iter().filter(|&d| d == 1).count();
+    //Topmost digit that isn't `transparent`, stays `transparent` if all layers are
+    pub fn composite(&self, transparent: u8) -> Grid<u8> {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            let ix = y * self.width + x;
+            self.layers_iter()
+                .map(|l| self.data[l.ix + ix])
+                .find(|&d| d != transparent)
+                .unwrap_or(transparent)
+        })
+    }
 
-        let twos = min_layer.digits_iter().filter(|&d| d == 2).count();
+    fn draw_image(&self) -> Vec<Vec<u8>> {
+        self.composite(2)
+            .rows()
+            .map(|row| row.map(|&d| if d == 2 { 0 } else { d }).collect())
+            .collect()
+    }
 
-        ones * twos
+    //All layers below each other
+    pub fn to_png(&self, path: impl AsRef<Path>) -> AocResult<()> {
+        let stacked = Grid::from_fn(self.width, self.height * self.layers_len(), |(x, y)| {
+            self.data[y * self.width + x]
+        });
+
+        Renderer::new(digit_style).save_png(&stacked, path)
     }
 
-    fn draw_image(&self) -> Vec<Vec<u8>> {
-        self.layers_iter()
-            .rev()
-            .fold(vec![vec![0; self.width]; self.height], |mut img, layer| {
-                for (x, y, pixel) in layer.rows_iter().enumerate().flat_map(|(y, row)| {
-                    row.iter().enumerate().map(move |(x, pixel)| (x, y, pixel))
-                }) {
-                    match pixel {
-                        0 => img[y][x] = 0,
-                        1 => img[y][x] = 1,
-                        _ => {}
-                    }
-                }
-
-                img
+    //Reverse of `to_png`, layers are `height` pixel rows each
+    pub fn from_png(path: impl AsRef<Path>, height: usize) -> AocResult<Image> {
+        let img = image::open(path)
+            .map_err(|e| AocErr::Other(Box::new(e)))?
+            .to_rgb();
+        let (width, total) = (img.width() as usize, img.height() as usize);
+        if height == 0 || total % height != 0 {
+            return Err(custom_err(format!(
+                "Image height {} is no multiple of {}",
+                total, height
+            )));
+        }
+
+        let data = img
+            .enumerate_pixels()
+            .map(|(x, y, p)| {
+                COLORS
+                    .iter()
+                    .position(|c| *c == p.0)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| custom_err(format!("Unknown color {:?} at ({}, {})", p.0, x, y)))
             })
+            .collect::<AocResult<Vec<_>>>()?;
+
+        Ok(Image {
+            data,
+            width,
+            height,
+        })
     }
 }
 
-fn print_image(img: &Image) {
+fn render_image(img: &Image) -> String {
     let img = img.draw_image();
     let pallet = [' ', '#', 'O'];
 
-    for r in img.iter() {
-        for &pixel in r.iter() {
-            print!("{}", pallet[pixel as usize]);
-        }
-        println!();
+    img.iter()
+        .map(|r| {
+            r.iter()
+                .map(|&pixel| pallet[pixel as usize])
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn read_image(img: &Image) -> AocResult<String> {
+    let rows = img
+        .draw_image()
+        .into_iter()
+        .map(|r| r.into_iter().map(|pixel| pixel == 1).collect())
+        .collect();
+
+    ocr::read(&Grid::from_rows(rows)?)
+}
+
+fn print_image(img: &Image) {
+    println!("{}", render_image(img));
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: usize = 8;
+    type Data = Image;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> AocResult<Image> {
+        Image::decode(input, 25, 6)
+    }
+
+    fn part1(img: &Image) -> AocResult<usize> {
+        Ok(img.lowest_layer())
+    }
+
+    fn part2(img: &Image) -> AocResult<String> {
+        read_image(img)
     }
 }
 
@@ -142,9 +336,7 @@ mod tests {
 
         let data: Data = parse_file(FileType::Input, 8, 1)?;
         let img = Image::from_data(data, 6, 25);
-        assert_eq!(img.lowest_layer(), 2806);
-
-        Ok(())
+        check_answer(FileType::Input, 8, 1, 1, img.lowest_layer())
     }
 
     #[test]
@@ -157,7 +349,49 @@ mod tests {
 
         let data: Data = parse_file(FileType::Input, 8, 1)?;
         let img = Image::from_data(data, 6, 25);
-        print_image(&img);
+        check_answer(FileType::Input, 8, 1, 2, read_image(&img)?)
+    }
+
+    #[test]
+    fn test_format() -> AocResult<()> {
+        let img = Image::decode("0222112222120000\n", 2, 2)?;
+        assert_eq!((2, 2, 4), (img.width(), img.height(), img.layers_len()));
+        assert!(img.layer(3).is_some() && img.layer(4).is_none());
+        assert_eq!(
+            [1, 0, 3, 0, 0, 0, 0, 0, 0, 0],
+            img.layer(0).unwrap().histogram()
+        );
+        assert_eq!("0222112222120000", img.encode());
+
+        let composite = img.composite(2);
+        assert_eq!(
+            vec![0, 1, 1, 0],
+            composite.values().cloned().collect::<Vec<_>>()
+        );
+        let composite = img.composite(0);
+        assert_eq!(
+            vec![1, 2, 2, 2],
+            composite.values().cloned().collect::<Vec<_>>()
+        );
+
+        let layers: Vec<_> = img.layers_iter().map(|l| l.to_grid()).collect();
+        assert_eq!(img.encode(), Image::from_layers(&layers)?.encode());
+        let ragged = vec![layers[0].clone(), Grid::new(2, 1, 0)];
+        assert!(Image::from_layers(&ragged).is_err());
+        assert!(Image::from_layers(&[Grid::new(1, 1, 12)]).is_err());
+
+        let err = Image::decode("012x", 2, 2).err().unwrap();
+        assert_eq!("<input>:1:4: Invalid digit at `x`", err.to_string());
+        let err = Image::decode("0123012", 2, 2).err().unwrap();
+        assert!(err.to_string().contains("Partial layer, 3 of 4 digits"));
+        assert!(Image::decode("", 2, 2).is_err() && Image::decode("00", 0, 2).is_err());
+
+        let path = std::env::temp_dir().join(format!("aoc19_day08_{}.png", std::process::id()));
+        img.to_png(&path)?;
+        let read = Image::from_png(&path, 2)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(img.encode(), read.encode());
+        assert_eq!(4, read.layers_len());
 
         Ok(())
     }
@@ -1,7 +1,31 @@
 #![allow(dead_code)]
 
+use crate::days::day05::{Context, Data};
+use crate::solution::Solution;
+use crate::*;
 
-const DAY: usize = 09;
+const DAY: usize = 9;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: usize = DAY;
+    type Data = Data;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<isize> {
+        Context::from_data_fill_up(data.clone(), &[1]).exec()
+    }
+
+    fn part2(data: &Data) -> AocResult<isize> {
+        Context::from_data_fill_up(data.clone(), &[2]).exec()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -29,23 +53,19 @@ mod tests {
         assert_eq!(1_125_899_906_842_624, ctx.output().unwrap());
         assert!(ctx.halted());
 
-        let data = parse_file(FileType::Input, DAY, 01)?;
+        let data = parse_file(FileType::Input, DAY, 1)?;
         let mut ctx = Context::from_data_fill_up(data, &[1]);
 
         ctx.exec()?;
-        assert_eq!(3_235_019_597, ctx.output().unwrap());
-
-        Ok(())
+        check_answer(FileType::Input, DAY, 1, 1, ctx.output().unwrap())
     }
 
     #[test]
     fn part2() -> AocResult<()> {
-        let data = parse_file(FileType::Input, DAY, 01)?;
+        let data = parse_file(FileType::Input, DAY, 1)?;
         let mut ctx = Context::from_data_fill_up(data, &[2]);
 
         ctx.exec()?;
-        assert_eq!(80274, ctx.output().unwrap());
-
-        Ok(())
+        check_answer(FileType::Input, DAY, 1, 2, ctx.output().unwrap())
     }
 }
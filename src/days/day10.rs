@@ -1,11 +1,12 @@
 #![allow(dead_code)]
 
+use crate::solution::Solution;
 use crate::*;
 use num::Integer;
 use std::collections::HashSet;
 use std::str::FromStr;
-use ndarray::Array2;
-use itertools::Itertools;
+use crate::helper::grid::Grid;
+use crate::helper::vec::Vec2;
 
 const DAY: usize = 10;
 
@@ -19,53 +20,32 @@ impl FromStr for Grid<GridField> {
     type Err = AocErr;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.lines().next().map(|l| l.chars().count()).unwrap_or(0);
-        let height = s.lines().count();
-
-        let v = s
-            .lines()
-            .flat_map(|l| l.chars())
-            .map(|c| match c {
-                '#' => GridField::Asteroid,
-                '.' => GridField::Empty,
-                _ => unreachable!()
-            })
-            .collect_vec();
-
-        let mut arr = Array2::from_shape_vec((height, width), v)
-            .unwrap();
-        arr.swap_axes(1, 0);
-
-
-
-        Ok(Grid{
-            arr
+        Grid::parse(s, |c| match c {
+            '#' => Some(GridField::Asteroid),
+            '.' => Some(GridField::Empty),
+            _ => None,
         })
     }
 }
 
-
-pub struct Grid<T> {
-    arr: Array2<T>
-}
-
 impl Grid<GridField> {
-    pub fn asteroids(&self) -> impl Iterator<Item = ((usize, usize), &GridField)> + '_ {
-        self.arr.indexed_iter()
-            .filter(|f|  match f.1 {
-                &GridField::Empty => false,
-                &GridField::Asteroid => true,
+    pub fn asteroids(&self) -> impl Iterator<Item = (Vec2, &GridField)> + '_ {
+        self.iter()
+            .filter(|f|  match *f.1 {
+                GridField::Empty => false,
+                GridField::Asteroid => true,
             })
+            .map(|(p, f)| (Vec2::from_index(p), f))
     }
 
 
 
-    fn visible_asteroids(&self, from: (usize, usize)) -> impl Iterator<Item = ((usize, usize), &GridField)> + '_ {
+    fn visible_asteroids(&self, from: Vec2) -> impl Iterator<Item = (Vec2, &GridField)> + '_ {
         self.asteroids()
             .filter(move |(to, _)| self.is_visible(from, *to))
     }
 
-    fn best_place(&self) -> Option<((usize, usize), usize)> {
+    fn best_place(&self) -> Option<(Vec2, usize)> {
         self.asteroids()
             .map(|(from, _)| {
                 let n = self
@@ -78,11 +58,11 @@ impl Grid<GridField> {
             .max_by_key(|(_, count)| *count)
     }
 
-    fn calc_degree(from: (usize, usize), to: (usize, usize)) -> f64 {
+    fn calc_degree(from: Vec2, to: Vec2) -> f64 {
         use std::f64::consts::*;
 
-        let slope_x = (to.0 as f64) - (from.0 as f64);
-        let slope_y = (to.1 as f64) - (from.1 as f64);
+        let slope = to - from;
+        let (slope_x, slope_y) = (slope.x as f64, slope.y as f64);
 
         let rad = slope_y.atan2(slope_x);
         let rad = match rad {
@@ -101,7 +81,7 @@ impl Grid<GridField> {
         rad * 180. / PI
     }
 
-    fn vaporize(&mut self, from: (usize, usize)) -> Vec<(usize, usize)> {
+    fn vaporize(&mut self, from: Vec2) -> Vec<Vec2> {
         let mut vap_seq = Vec::new();
         let mut vaporized = HashSet::new();
 
@@ -116,7 +96,7 @@ impl Grid<GridField> {
                 return vap_seq;
             }
 
-            ast.sort_by_key(|(_, deg)| deg.clone());
+            ast.sort_by_key(|(_, deg)| *deg);
 
             for (pt, _) in ast.iter().cloned() {
                 vaporized.insert(pt);
@@ -124,39 +104,56 @@ impl Grid<GridField> {
             }
 
             for vap in vaporized.iter().cloned() {
-                self.arr[vap] = GridField::Empty;
+                self[vap] = GridField::Empty;
             }
         }
     }
 
-    fn is_visible(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+    fn is_visible(&self, from: Vec2, to: Vec2) -> bool {
         if from == to {
             return false;
         }
 
-        let x_delta = (to.0 as isize) - (from.0 as isize);
-        let y_delta = (to.1 as isize) - (from.1 as isize);
-
-        let div = match (x_delta, y_delta) {
-            (0, y) => y.abs(),
-            (x, 0) => x.abs(),
-            (x, y) => x.gcd(&y),
-        };
-
-        let x_delta = x_delta / div;
-        let y_delta = y_delta / div;
+        let delta = to - from;
+        let div = delta.x.gcd(&delta.y);
+        let step = Vec2::new(delta.x / div, delta.y / div);
 
         (1..div)
-            .map(|k| {
-                let x = (from.0 as isize) + x_delta * k;
-                let y = (from.1 as isize) + y_delta * k;
-                (x as usize, y as usize)
-            })
-            .all(|pt| self.arr[pt] != GridField::Asteroid)
+            .map(|k| from + step * k)
+            .all(|pt| self[pt] != GridField::Asteroid)
     }
 }
 
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = DAY;
+    type Data = Grid<GridField>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AocResult<Grid<GridField>> {
+        input.parse()
+    }
+
+    fn part1(grid: &Grid<GridField>) -> AocResult<usize> {
+        let (_, count) = grid.best_place().ok_or_else(|| custom_err("No asteroids"))?;
+        Ok(count)
+    }
+
+    fn part2(grid: &Grid<GridField>) -> AocResult<usize> {
+        let (from, _) = grid.best_place().ok_or_else(|| custom_err("No asteroids"))?;
+        let pt = *grid
+            .clone()
+            .vaporize(from)
+            .get(199)
+            .ok_or_else(|| custom_err("Less than 200 asteroids"))?;
+
+        Ok((pt.x * 100 + pt.y) as usize)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,53 +161,51 @@ mod tests {
     #[test]
     fn part1() -> AocResult<()> {
         let grid: Grid<GridField> = parse_file(FileType::Example, DAY, 1)?;
-        assert!(!grid.is_visible((4, 4), (4, 0)));
-        assert_eq!(((3, 4), 8), grid.best_place().unwrap());
+        assert!(!grid.is_visible(Vec2::new(4, 4), Vec2::new(4, 0)));
+        assert_eq!((Vec2::new(3, 4), 8), grid.best_place().unwrap());
 
         let grid: Grid<GridField> = parse_file(FileType::Example, DAY, 2)?;
-        assert_eq!(((5, 8), 33), grid.best_place().unwrap());
+        assert_eq!((Vec2::new(5, 8), 33), grid.best_place().unwrap());
 
         let grid: Grid<GridField> = parse_file(FileType::Example, DAY, 3)?;
-        assert_eq!(((1, 2), 35), grid.best_place().unwrap());
+        assert_eq!((Vec2::new(1, 2), 35), grid.best_place().unwrap());
 
         let grid: Grid<GridField> = parse_file(FileType::Example, DAY, 4)?;
-        assert_eq!(((6, 3), 41), grid.best_place().unwrap());
+        assert_eq!((Vec2::new(6, 3), 41), grid.best_place().unwrap());
 
         let grid: Grid<GridField> = parse_file(FileType::Example, DAY, 5)?;
-        assert_eq!(((11, 13), 210), grid.best_place().unwrap());
+        assert_eq!((Vec2::new(11, 13), 210), grid.best_place().unwrap());
 
         let grid: Grid<GridField> = parse_file(FileType::Input, DAY, 1)?;
-        assert_eq!(((23, 29), 263), grid.best_place().unwrap());
-
-        Ok(())
+        check_answer(FileType::Input, DAY, 1, 1, grid.best_place().unwrap().1)
     }
 
     #[test]
     fn part2() -> AocResult<()> {
         //TODO use nearly equal
-        assert_eq!(0., Grid::calc_degree((1, 1), (1, 0)));
-        assert_eq!(90., Grid::calc_degree((1, 1), (2, 1)));
-        assert_eq!(180., Grid::calc_degree((1, 1), (1, 2)));
-        assert_eq!(270., Grid::calc_degree((1, 1), (0, 1)));
+        assert_eq!(0., Grid::calc_degree(Vec2::new(1, 1), Vec2::new(1, 0)));
+        assert_eq!(90., Grid::calc_degree(Vec2::new(1, 1), Vec2::new(2, 1)));
+        assert_eq!(180., Grid::calc_degree(Vec2::new(1, 1), Vec2::new(1, 2)));
+        assert_eq!(270., Grid::calc_degree(Vec2::new(1, 1), Vec2::new(0, 1)));
 
-        assert_eq!(90. + 45., Grid::calc_degree((2, 2), (3, 3)));
-        assert_eq!(45., Grid::calc_degree((2, 2), (3, 1)));
-        assert_eq!(180. + 45., Grid::calc_degree((2, 2), (1, 3)));
-        assert_eq!(270. + 45., Grid::calc_degree((2, 2), (1, 1)));
+        assert_eq!(90. + 45., Grid::calc_degree(Vec2::new(2, 2), Vec2::new(3, 3)));
+        assert_eq!(45., Grid::calc_degree(Vec2::new(2, 2), Vec2::new(3, 1)));
+        assert_eq!(180. + 45., Grid::calc_degree(Vec2::new(2, 2), Vec2::new(1, 3)));
+        assert_eq!(270. + 45., Grid::calc_degree(Vec2::new(2, 2), Vec2::new(1, 1)));
 
         let mut grid: Grid<GridField> = parse_file(FileType::Example, DAY, 6)?;
         itertools::assert_equal(
-            [(8, 1), (9, 0), (9, 1)].iter(),
-            grid.vaporize((8, 3)).iter().take(3),
+            [Vec2::new(8, 1), Vec2::new(9, 0), Vec2::new(9, 1)].iter(),
+            grid.vaporize(Vec2::new(8, 3)).iter().take(3),
         );
 
         let mut grid: Grid<GridField> = parse_file(FileType::Example, DAY, 5)?;
-        let vaps = grid.vaporize((11, 13));
-        assert_eq!(vaps.iter().position(|pt| *pt == (8, 2)).unwrap(), 198);
+        let vaps = grid.vaporize(Vec2::new(11, 13));
+        assert_eq!(vaps.iter().position(|pt| *pt == Vec2::new(8, 2)).unwrap(), 198);
 
         let mut grid: Grid<GridField> = parse_file(FileType::Input, DAY, 1)?;
-        let vaps = grid.vaporize((23, 29));
-        assert_eq!(vaps[199], (11, 10));
-        Ok(())
+        let (best, _) = grid.best_place().unwrap();
+        let pt = grid.vaporize(best)[199];
+        check_answer(FileType::Input, DAY, 1, 2, pt.x * 100 + pt.y)
     }
 }
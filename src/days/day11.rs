@@ -1,12 +1,15 @@
 #![allow(dead_code)]
 
+use crate::solution::Solution;
 use crate::*;
 use days::day05::{Data, Context};
 use std::convert::{TryFrom};
-use image::ImageBuffer;
-use ndarray::Array2;
+use crate::helper::sparse::SparseGrid;
 use crate::helper::dir::{Dir};
-use geo::Point;
+use crate::helper::ocr;
+use crate::helper::render::{self, Renderer};
+use std::path::Path;
+use crate::helper::vec::Vec2;
 
 const DAY: usize = 11;
 
@@ -28,29 +31,26 @@ impl TryFrom<u8> for Color {
     }
 }
 
-impl Into<u8> for Color {
-    fn into(self) -> u8 {
-        match self {
+impl From<Color> for u8 {
+    fn from(color: Color) -> u8 {
+        match color {
             Color::Black => 0,
             Color::White => 1
         }
     }
 }
 
-fn run(data: Data, start: Color, save: bool) -> AocResult<usize> {
-    const N: usize = 512;
-    const CENTER: usize = N/2;
-
-    let mut grid = Array2::from_elem((N, N), (start,0));
-    grid.swap_axes(1, 0);
+fn paint(data: Data, start: Color) -> AocResult<SparseGrid<(Color, usize)>> {
+    let mut grid = SparseGrid::new((Color::Black, 0));
+    grid.set(Vec2::ZERO, (start, 0));
 
     let mut ctx = Context::from_data_fill_up(data, &[]);
-    let mut pos = Point::new(CENTER, CENTER);
+    let mut pos = Vec2::ZERO;
     let mut dir = Dir::North;
 
 
     loop {
-        let color: u8 = grid[pos.x_y()].0.into();
+        let color: u8 = grid[pos].0.into();
         ctx.push_input(color as isize);
 
         ctx.resume()?;
@@ -63,9 +63,9 @@ fn run(data: Data, start: Color, save: bool) -> AocResult<usize> {
         let turn = ctx.pop_output().unwrap();
         let color = ctx.pop_output().unwrap();
 
-        let counter = grid[pos.x_y()].1;
+        let counter = grid[pos].1;
         let color = Color::try_from(color as u8)?;
-        grid[pos.x_y()] = (color, counter+1);
+        grid.set(pos, (color, counter+1));
 
         dir = match turn {
             0 => dir.left(),
@@ -73,23 +73,61 @@ fn run(data: Data, start: Color, save: bool) -> AocResult<usize> {
             _ => unreachable!()
         };
 
-        pos = dir.next_pos(pos);
+        pos = dir.step(pos);
     }
 
-    if save {
-        let img = ImageBuffer::from_fn(N as u32, N as u32, |x, y| {
-           match grid[(x as usize,y as usize)].0 {
-               Color::Black => image::Luma([0u8]),
-               Color::White => image::Luma([255u8])
-           }
-        });
+    Ok(grid)
+}
+
+fn render_hull(grid: &SparseGrid<(Color, usize)>) -> String {
+    grid.crop(|g| matches!(g.0, Color::White))
+        .0
+        .render(|g| match g.0 {
+            Color::Black => ' ',
+            Color::White => '#',
+        })
+}
+
+fn read_hull(grid: &SparseGrid<(Color, usize)>) -> AocResult<String> {
+    let (hull, _) = grid.crop(|g| matches!(g.0, Color::White));
+    ocr::read(&hull.map(|g| matches!(g.0, Color::White)))
+}
+
+fn run(data: Data, start: Color) -> AocResult<usize> {
+    let grid = paint(data, start)?;
+    Ok(grid.iter().filter(|(_, g)| g.1 > 0).count())
+}
+
+//Registration hull as png, 4x4 pixels per panel
+pub fn save_hull(data: &Data, path: impl AsRef<Path>) -> AocResult<()> {
+    let (hull, _) = paint(data.clone(), Color::White)?.to_grid();
+    let style = |g: &(Color, usize)| match g.0 {
+        Color::Black => render::BLACK,
+        Color::White => render::WHITE,
+    };
+
+    Renderer::new(style).scale(4).save_png(&hull, path)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: usize = DAY;
+    type Data = Data;
+    type Answer1 = usize;
+    type Answer2 = String;
 
-        img.save("out11.png").unwrap();
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
     }
 
-    Ok(grid.iter()
-        .filter(|g| g.1 > 0)
-        .count())
+    fn part1(data: &Data) -> AocResult<usize> {
+        run(data.clone(), Color::Black)
+    }
+
+    fn part2(data: &Data) -> AocResult<String> {
+        read_hull(&paint(data.clone(), Color::White)?)
+    }
 }
 
 #[cfg(test)]
@@ -98,15 +136,13 @@ mod tests {
 
     #[test]
     fn part1() -> AocResult<()> {
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
-        assert_eq!(run(data, Color::Black, false)?, 1909);
-        Ok(())
+        let data: Data = parse_file(FileType::Input, DAY, 1)?;
+        check_answer(FileType::Input, DAY, 1, 1, run(data, Color::Black)?)
     }
 
     #[test]
     fn part2() -> AocResult<()> {
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
-        assert_eq!(run(data, Color::White, true)?, 249);
-        Ok(())
+        let data: Data = parse_file(FileType::Input, DAY, 1)?;
+        check_answer(FileType::Input, DAY, 1, 2, read_hull(&paint(data, Color::White)?)?)
     }
-}
\ No newline at end of file
+}
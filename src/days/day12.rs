@@ -1,97 +1,64 @@
 #![allow(dead_code)]
 
+use crate::helper::cycle;
+use crate::helper::vec::Vec3;
+use crate::parse::Cursor;
+use crate::solution::Solution;
 use crate::*;
-use itertools::Itertools;
-use num::integer::Integer;
-use std::cmp::Ordering;
 
 const DAY: usize = 12;
 
-pub struct Data([(isize, isize, isize); 4]);
+#[derive(Clone, Copy)]
+pub struct Data([Vec3; 4]);
 
 #[derive(Debug)]
 pub struct Moon {
-    pos: (isize, isize, isize),
-    vel: (isize, isize, isize),
+    pos: Vec3,
+    vel: Vec3,
 }
 
 impl FromStr for Data {
     type Err = AocErr;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let v = s
-            .lines()
-            .map(|l| {
-                let l = l.trim_start_matches('<');
-                let l = l.trim_end_matches('>');
-
-                let (mut x, mut y, mut z) = (0, 0, 0);
-                for (i, n) in l.split(',').enumerate() {
-                    let pos = n.find('=').ok_or(custom_err("No = divider"))?;
-
-                    let (name, val) = n.split_at(pos + 1);
-
-                    match (name.trim(), val.trim()) {
-                        ("x=", val) if i == 0 => x = val.parse()?,
-                        ("y=", val) if i == 1 => y = val.parse()?,
-                        ("z=", val) if i == 2 => z = val.parse()?,
-                        _ => unreachable!(),
-                    }
-                }
-
-                Ok((x, y, z))
-            })
-            .collect::<Result<Vec<(isize, isize, isize)>, AocErr>>()?;
-
-        let mut data = [(0, 0, 0); 4];
-        data.copy_from_slice(&v);
-        Ok(Data(data))
-    }
-}
+        let mut c = Cursor::new(s);
+        let v = c.lines(|c| {
+            c.ws().tag("<")?;
+            let pos = c.record(&["x", "y", "z"], |c| c.int())?;
+            c.ws().tag(">")?;
 
-fn abs_sum(p: (isize, isize, isize)) -> isize {
-    p.0.abs() + p.1.abs() + p.2.abs()
-}
+            Ok(Vec3::new(pos[0], pos[1], pos[2]))
+        })?;
 
-fn add(p: (isize, isize, isize), off: (isize, isize, isize)) -> (isize, isize, isize) {
-    (p.0 + off.0, p.1 + off.1, p.2 + off.2)
-}
+        if v.len() != 4 {
+            return Err(c.error_at(s, format!("Expected 4 moons, got {}", v.len())));
+        }
 
-fn calc_single_vel_offset(moon_pos: isize, other_pos: isize) -> isize {
-    match moon_pos.cmp(&other_pos) {
-        Ordering::Equal => 0,
-        Ordering::Greater => -1,
-        Ordering::Less => 1,
+        let mut data = [Vec3::ZERO; 4];
+        data.copy_from_slice(&v);
+        Ok(Data(data))
     }
 }
 
-fn calc_vel_offset(vel: (isize, isize, isize), moon: &Moon, other: &Moon) -> (isize, isize, isize) {
-    (
-        vel.0 + calc_single_vel_offset(moon.pos.0, other.pos.0),
-        vel.1 + calc_single_vel_offset(moon.pos.1, other.pos.1),
-        vel.2 + calc_single_vel_offset(moon.pos.2, other.pos.2),
-    )
-}
-
 fn update_vel(moons: &mut [Moon; 4]) {
     for moon in 0..moons.len() {
         moons[moon].vel = (0..moons.len())
             .filter(|j| *j != moon)
             .fold(moons[moon].vel, |vel, other| {
-                calc_vel_offset(vel, &moons[moon], &moons[other])
+                vel + (moons[other].pos - moons[moon].pos).signum()
             });
     }
 }
 
 fn update_pos(moons: &mut [Moon; 4]) {
     for moon in moons.iter_mut() {
-        moon.pos = add(moon.pos, moon.vel);
+        moon.pos += moon.vel;
     }
 }
 
 fn create_moons(data: Data) -> [Moon; 4] {
     let d = data.0;
-    let zero = (0, 0, 0);
+    let zero = Vec3::ZERO;
 
     [
         Moon {
@@ -113,56 +80,25 @@ fn create_moons(data: Data) -> [Moon; 4] {
     ]
 }
 
-fn check_axis_repeat(
-    moons: &[Moon; 4],
-    start: [(isize, isize, isize); 4],
-    axis_fn: impl Fn(&(isize, isize, isize)) -> isize,
-) -> bool {
-    moons
-        .iter()
-        .zip(start.iter())
-        .all(|(moon, start)| axis_fn(&moon.pos) == axis_fn(start) && axis_fn(&moon.vel) == 0)
+fn step(moons: &mut [Moon; 4]) {
+    update_vel(moons);
+    update_pos(moons);
 }
 
-fn find_prev(data: Data) -> usize {
-    let points = data.0.clone();
-    let mut moons = create_moons(data);
-    let mut intervals = [None; 3];
-
-    for i in 0_usize.. {
-        update_vel(&mut moons);
-        update_pos(&mut moons);
-
-        let i = i + 1;
-
-        //x
-        if intervals[0].is_none() && check_axis_repeat(&moons, points, |&(x, _, _)| x) {
-            intervals[0] = Some(i);
-        }
-
-        //y
-        if intervals[1].is_none() && check_axis_repeat(&moons, points, |&(_, y, _)| y) {
-            intervals[1] = Some(i);
-        }
-
-        //z
-        if intervals[2].is_none() && check_axis_repeat(&moons, points, |&(_, _, z)| z) {
-            intervals[2] = Some(i);
-        }
-
-        if intervals.iter().all(|iv| iv.is_some()) {
-            let (x, y, z) = intervals
-                .iter()
-                .map(|i| i.unwrap())
-                .collect_tuple()
-                .unwrap();
-
-            let yz = y.lcm(&z);
-            return x.lcm(&yz);
-        }
+fn axis_state(moons: &[Moon; 4], axis: usize) -> [(isize, isize); 4] {
+    let mut state = [(0, 0); 4];
+    for (s, m) in state.iter_mut().zip(moons.iter()) {
+        *s = (m.pos[axis], m.vel[axis]);
     }
 
-    unreachable!()
+    state
+}
+
+//Axes move independently and the simulation is reversible, so each axis returns to its start
+fn find_prev(data: Data) -> usize {
+    let mut moons = create_moons(data);
+    let periods = cycle::component_periods(&mut moons, step, 3, axis_state);
+    cycle::lcm_all(&periods)
 }
 
 fn calc_energy(data: Data, steps: usize) -> isize {
@@ -179,52 +115,78 @@ fn calc_energy(data: Data, steps: usize) -> isize {
     moons
         .iter()
         .map(|m| {
-            let pot = abs_sum(m.pos);
-            let kin = abs_sum(m.vel);
+            let pot = m.pos.manhattan() as isize;
+            let kin = m.vel.manhattan() as isize;
             pot * kin
         })
         .sum()
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: usize = DAY;
+    type Data = Data;
+    type Answer1 = isize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<isize> {
+        Ok(calc_energy(*data, 1000))
+    }
+
+    fn part2(data: &Data) -> AocResult<usize> {
+        Ok(find_prev(*data))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_parse() -> AocResult<()> {
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
-
-        assert_eq!((17, -9, 4), data.0[0]);
-        assert_eq!((2, 2, -13), data.0[1]);
-        assert_eq!((-1, 5, -1), data.0[2]);
-        assert_eq!((4, 7, -7), data.0[3]);
+        let data: Data = parse_file(FileType::Example, DAY, 1)?;
+
+        assert_eq!(Vec3::new(-1, 0, 2), data.0[0]);
+        assert_eq!(Vec3::new(2, -10, -7), data.0[1]);
+        assert_eq!(Vec3::new(4, -8, 8), data.0[2]);
+        assert_eq!(Vec3::new(3, 5, -1), data.0[3]);
+
+        let err = "<x=1, y=2, z=3>\n<x=1, q=2, z=3>"
+            .parse::<Data>()
+            .err()
+            .unwrap();
+        assert_eq!("<input>:2:7: Expected key `y` at `q`", err.to_string());
+        let err = "<x=1, y=2, z=3>".parse::<Data>().err().unwrap();
+        assert!(err.to_string().contains("Expected 4 moons, got 1"));
         Ok(())
     }
 
     #[test]
     fn part1() -> AocResult<()> {
-        let data: Data = parse_file(FileType::Example, DAY, 01)?;
+        let data: Data = parse_file(FileType::Example, DAY, 1)?;
         assert_eq!(179, calc_energy(data, 10));
 
-        let data: Data = parse_file(FileType::Example, DAY, 02)?;
+        let data: Data = parse_file(FileType::Example, DAY, 2)?;
         assert_eq!(1940, calc_energy(data, 100));
 
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
-        assert_eq!(7202, calc_energy(data, 1000));
-        Ok(())
+        let data: Data = parse_file(FileType::Input, DAY, 1)?;
+        check_answer(FileType::Input, DAY, 1, 1, calc_energy(data, 1000))
     }
 
     #[test]
     fn part2() -> AocResult<()> {
-        let data: Data = parse_file(FileType::Example, DAY, 01)?;
+        let data: Data = parse_file(FileType::Example, DAY, 1)?;
         assert_eq!(2772, find_prev(data));
 
-        let data: Data = parse_file(FileType::Example, DAY, 02)?;
+        let data: Data = parse_file(FileType::Example, DAY, 2)?;
         assert_eq!(4_686_774_924, find_prev(data));
 
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
-        assert_eq!(537_881_600_740_876, find_prev(data));
-
-        Ok(())
+        let data: Data = parse_file(FileType::Input, DAY, 1)?;
+        check_answer(FileType::Input, DAY, 1, 2, find_prev(data))
     }
 }
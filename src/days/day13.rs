@@ -1,10 +1,12 @@
 #![allow(dead_code)]
 
+use crate::solution::Solution;
 use crate::*;
 use super::day05::*;
-use ndarray::{Array2, Axis};
+use crate::helper::grid::Grid;
+use crate::intcode::screen::{Event, Screen};
 use std::convert::{TryFrom, TryInto};
-use geo::Point;
+use crate::helper::vec::Vec2;
 use std::cmp::Ordering;
 
 const DAY: usize = 13;
@@ -18,16 +20,16 @@ pub enum Tile {
     Ball
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Input {
     Neutral,
     Left,
     Right
 }
 
-impl Into<isize> for Input {
-    fn into(self) -> isize {
-        match self {
+impl From<Input> for isize {
+    fn from(input: Input) -> isize {
+        match input {
             Input::Neutral => 0,
             Input::Left => -1,
             Input::Right => 1
@@ -50,157 +52,148 @@ impl TryFrom<u8> for Tile {
     }
 }
 
+//What a player gets to see of the game
+pub struct Observation<'a> {
+    pub tiles: &'a Grid<Tile>,
+    pub ball: Vec2,
+    pub paddle: Vec2,
+    pub velocity: Vec2,
+    pub score: usize
+}
+
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    fn input(&mut self, obs: &Observation) -> Input;
+}
+
+//Keeps the paddle below the ball
+pub struct Greedy;
+
+impl Strategy for Greedy {
+    fn name(&self) -> &str {
+        "greedy"
+    }
+
+    fn input(&mut self, obs: &Observation) -> Input {
+        match obs.ball.x.cmp(&obs.paddle.x) {
+            Ordering::Equal => Input::Neutral,
+            Ordering::Greater => Input::Right,
+            Ordering::Less => Input::Left
+        }
+    }
+}
+
 pub struct Game  {
-    ctx: Context,
-    tile_grid: Array2<Tile>,
+    screen: Screen<Tile>,
     score: usize,
-    ball_pos: Point<usize>,
-    paddle_pos: Point<usize>,
-    ball_vel: (isize, isize)
+    ball_pos: Option<Vec2>,
+    paddle_pos: Option<Vec2>,
+    ball_vel: Vec2
+}
+
+fn tile_from(value: isize) -> AocResult<Tile> {
+    u8::try_from(value)
+        .map_err(|_| custom_err(format!("Invalid tile: {}", value)))?
+        .try_into()
 }
 
 impl Game {
     pub fn create(data: Data, play: bool) -> AocResult<Game> {
         let mut ctx = Context::from_data_fill_up(data, &[]);
 
-        let mut grid = Array2::from_elem((30, 50), Tile::Empty);
-        grid.swap_axes(1, 0);
-
         if play {
             ctx.data_mut()[0] = 2;
         }
 
-        loop {
-            ctx.resume()?;
-            ctx.resume()?;
-            ctx.resume()?;
-
-            if ctx.halted() {
-                break;
-            }
-
-            let tile_id = ctx.pop_output().unwrap();
-            let y = ctx.pop_output().unwrap();
-            let x = ctx.pop_output().unwrap();
-
-            //Detect game start
-            if x == -1 && y == 0 {
-                break;
-            }
+        let mut game = Game{
+            screen: Screen::new(ctx, Tile::Empty, tile_from),
+            score: 0,
+            ball_pos: None,
+            paddle_pos: None,
+            ball_vel: Vec2::new(1, 1)
+        };
+        game.run_frame()?;
 
-            Self::draw(&mut grid, tile_id, x, y)?;
+        //Only playing needs them
+        if play {
+            game.observe()?;
         }
 
-
-        let find_pos = |tile_kind: Tile| grid.indexed_iter()
-            .filter(|(_, tile)| tile == &&tile_kind)
-            .map(|(p, _)| Point::new(p.0, p.1))
-            .next()
-            .unwrap();
-
-        let ball_pos = find_pos(Tile::Ball);
-        let paddle_pos = find_pos(Tile::HorizontalPaddle);
-
-
-        Ok(Game{
-            ctx,
-            tile_grid: grid,
-            score: 0,
-            ball_pos,
-            paddle_pos,
-            ball_vel: (1, 1)
-        })
+        Ok(game)
     }
 
     pub fn shape(&self) -> (usize, usize) {
-        let shape = self.tile_grid.shape();
-        (shape[0], shape[1])
+        (self.tiles().width(), self.tiles().height())
     }
 
-    pub fn ball(&self) -> Point<usize> {
+    pub fn ball(&self) -> Option<Vec2> {
         self.ball_pos
     }
 
-    pub fn paddle(&self) -> Point<usize> {
+    pub fn paddle(&self) -> Option<Vec2> {
         self.paddle_pos
     }
 
-    pub fn walls(&self) -> impl Iterator<Item=Point<usize>> + '_ {
-        self.tile_grid.indexed_iter()
-            .filter(|(_, tile)| tile == &&Tile::Wall)
-            .map(|(p, _)| Point::new(p.0, p.1))
+    pub fn tiles(&self) -> &Grid<Tile> {
+        self.screen.grid()
     }
 
-    pub fn blocks(&self) -> impl Iterator<Item=Point<usize>> + '_ {
-        self.tile_grid.indexed_iter()
-            .filter(|(_, tile)| tile == &&Tile::Block)
-            .map(|(p, _)| Point::new(p.0, p.1))
+    pub fn halted(&self) -> bool {
+        self.screen.halted()
+    }
+
+    pub fn observe(&self) -> AocResult<Observation<'_>> {
+        Ok(Observation {
+            tiles: self.tiles(),
+            ball: self.ball_pos.ok_or_else(|| custom_err("No ball on screen"))?,
+            paddle: self.paddle_pos.ok_or_else(|| custom_err("No paddle on screen"))?,
+            velocity: self.ball_vel,
+            score: self.score
+        })
     }
 
-    fn draw(grid: &mut Array2<Tile>, tile_id: isize, x: isize, y: isize) -> AocResult<()>
-    {
-        let pt = Point::new(x as usize, y as usize);
-        let tile = Tile::try_from(tile_id as u8)?;
+    pub fn walls(&self) -> impl Iterator<Item=Vec2> + '_ {
+        self.tiles().iter()
+            .filter(|(_, tile)| tile == &&Tile::Wall)
+            .map(|(p, _)| Vec2::from_index(p))
+    }
 
-        grid[pt.x_y()] = tile;
-        Ok(())
+    pub fn blocks(&self) -> impl Iterator<Item=Vec2> + '_ {
+        self.tiles().iter()
+            .filter(|(_, tile)| tile == &&Tile::Block)
+            .map(|(p, _)| Vec2::from_index(p))
     }
 
-    fn game_draw(&mut self, tile_id: isize, x: isize, y: isize) -> AocResult<()>
-    {
-        let tile: Tile = (tile_id as u8).try_into()?;
-        let pt = Point::new(x as usize, y as usize);
-        match tile {
-            Tile::Ball => {
-                let xy = pt.x_y();
-                let x_vel = (xy.0 as isize) - (self.ball_pos.x() as isize);
-                let y_vel = (xy.1 as isize) - (self.ball_pos.y() as isize);
-                self.ball_vel = (x_vel, y_vel);
-                self.ball_pos = pt
+    //Applies everything drawn until the game waits for input, false once it is over
+    fn run_frame(&mut self) -> AocResult<bool> {
+        let (score, ball, paddle, vel) = (&mut self.score, &mut self.ball_pos, &mut self.paddle_pos, &mut self.ball_vel);
+        let last = self.screen.frame(|event| match event {
+            Event::Draw(pt, Tile::Ball) => {
+                if let Some(old) = *ball {
+                    *vel = *pt - old;
+                }
+                *ball = Some(*pt)
             },
-            Tile::HorizontalPaddle => self.paddle_pos = pt,
+            Event::Draw(pt, Tile::HorizontalPaddle) => *paddle = Some(*pt),
+            Event::Score(val) => *score = *val as usize,
             _ => {}
-        };
+        })?;
 
-        self.tile_grid[pt.x_y()] = tile;
-        Ok(())
+        Ok(last == Event::Input)
     }
 
-    pub fn update(&mut self) -> AocResult<bool> {
-        let ctx = &mut self.ctx;
-        ctx.resume()?;
-        ctx.resume()?;
-        ctx.resume()?;
-
-        if ctx.halted() {
-            return Ok(false)
-        }
-
-        let val = ctx.pop_output().unwrap();
-        let y = ctx.pop_output().unwrap();
-        let x = ctx.pop_output().unwrap();
-
-        //Detect game start
-        if x == -1 && y == 0 {
-            self.score = val as usize;
-            Ok(false)
-        } else {
-            self.game_draw(val, x, y)?;
-
-            Ok(val == 4)
-        }
+    //One tick of the game: feeds `input` and runs until the next input is needed.
+    //Returns false once the game is over
+    pub fn step(&mut self, input: Input) -> AocResult<bool> {
+        self.screen.push_input(input.into());
+        self.run_frame()
     }
 
     pub fn auto_play(&mut self) -> AocResult<()> {
-        if !self.ctx.halted() {
-            if self.update()? {
-                let input = match self.ball_pos.x().cmp(&self.paddle_pos.x()) {
-                    Ordering::Equal => Input::Neutral,
-                    Ordering::Greater => Input::Right,
-                    Ordering::Less => Input::Left
-                };
-                self.ctx.push_input(input.into());
-            }
+        if !self.halted() {
+            let input = Greedy.input(&self.observe()?);
+            self.step(input)?;
         }
 
         Ok(())
@@ -210,55 +203,58 @@ impl Game {
         self.score
     }
 
-    fn play(mut self) -> AocResult<usize> {
-        self.ctx.push_input(Input::Neutral.into());
-        while !self.ctx.halted() {
-            if self.count_blocks() == 0 {
-                self.update()?;
-                return Ok(self.score);
-            }
-
-            if self.update()? {
-                let input = match self.ball_pos.x().cmp(&self.paddle_pos.x()) {
-                    Ordering::Equal => Input::Neutral,
-                    Ordering::Greater => Input::Right,
-                    Ordering::Less => Input::Left
-                };
-                self.ctx.push_input(input.into());
-            }
+    pub fn play_with(mut self, strategy: &mut dyn Strategy) -> AocResult<usize> {
+        while !self.halted() {
+            let input = strategy.input(&self.observe()?);
+            self.step(input)?;
         }
 
         Ok(self.score)
     }
 
-    pub fn set_input(&mut self, input: Input) {
-        self.ctx.push_input(input.into());
+    fn play(self) -> AocResult<usize> {
+        self.play_with(&mut Greedy)
     }
 
     fn count_blocks(&self) -> usize {
-        self.tile_grid.iter()
-            .filter(|tile| match tile {
-                Tile::Block => true,
-                _ => false
-            })
+        self.tiles().values()
+            .filter(|tile| matches!(tile, Tile::Block))
             .count()
     }
 
+    pub fn render(&self) -> String {
+        self.tiles().render(|tile| match tile {
+            Tile::Empty => ' ',
+            Tile::Ball => 'O',
+            Tile::HorizontalPaddle => '_',
+            Tile::Block => '*',
+            Tile::Wall => '#',
+        })
+    }
+
     pub fn print(&self) {
-        for row in self.tile_grid.lanes(Axis(0)) {
-            for tile in row.into_iter() {
-                let c = match tile {
-                    Tile::Empty => ' ',
-                    Tile::Ball => 'O',
-                    Tile::HorizontalPaddle => '_',
-                    Tile::Block => '*',
-                    Tile::Wall => '#'
-                };
-
-                print!("{}", c);
-            }
-            println!()
-        }
+        println!("{}", self.render());
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: usize = DAY;
+    type Data = Data;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<usize> {
+        Ok(Game::create(data.clone(), false)?.count_blocks())
+    }
+
+    fn part2(data: &Data) -> AocResult<usize> {
+        Game::create(data.clone(), true)?.play()
     }
 }
 
@@ -268,20 +264,29 @@ mod tests {
 
     #[test]
     fn part1() -> AocResult<()> {
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
+        let data: Data = parse_file(FileType::Input, DAY, 1)?;
         let game = Game::create(data, false)?;
-        assert_eq!(game.count_blocks(), 268);
-
-        Ok(())
+        check_answer(FileType::Input, DAY, 1, 1, game.count_blocks())
     }
 
     #[test]
     fn part2() -> AocResult<()> {
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
+        let data: Data = parse_file(FileType::Input, DAY, 1)?;
         let game = Game::create(data, true)?;
-        let score = game.play()?;
-        assert_eq!(score, 13989);
+        check_answer(FileType::Input, DAY, 1, 2, game.play()?)
+    }
 
+    #[test]
+    fn missing_sprites() -> AocResult<()> {
+        //Draws a single wall and waits for input, the first opcode becomes a harmless mul when playing
+        let data = Data(vec![1, 0, 0, 0, 104, 0, 104, 0, 104, 1, 3, 100, 99]);
+        let game = Game::create(data.clone(), false)?;
+        assert_eq!((1, 1), game.shape());
+        assert_eq!(0, game.count_blocks());
+        assert!(game.observe().is_err());
+
+        let err = Game::create(data, true).err().unwrap();
+        assert!(err.to_string().contains("No ball on screen"));
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,10 @@
 #![allow(dead_code)]
 
+use crate::helper::bisect;
+use crate::parse::Cursor;
+use crate::solution::Solution;
 use crate::*;
 use std::collections::{HashMap, VecDeque};
-use std::cmp::Ordering;
 
 const DAY: usize = 14;
 
@@ -15,17 +17,24 @@ impl FromStr for Chemical {
     type Err = AocErr;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let p = s.find(' ')
-            .ok_or_else(|| custom_err("No chemical split"))?;
-        let s = s.split_at(p);
-
-        Ok(Chemical {
-            name: s.1.trim().to_string(),
-            num: s.0.parse()?
-        })
+        Cursor::complete(s, chemical)
     }
 }
 
+fn chemical(c: &mut Cursor) -> AocResult<Chemical> {
+    let num = c.uint()?;
+    let name = c.ws().ident()?;
+
+    if !name.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(c.error_at(name, "Invalid chemical name"));
+    }
+
+    Ok(Chemical {
+        name: name.to_string(),
+        num,
+    })
+}
+
 pub struct Data {
    rules: HashMap<String, (usize, Vec<Chemical>)>
 }
@@ -34,33 +43,32 @@ impl FromStr for Data {
     type Err = AocErr;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let m= s.lines()
-            .map(|l| {
-                let p = l.find("=>")
-                    .ok_or_else(|| custom_err("Invalid rule"))?;
-
-                let (left, right) = l.split_at(p);
-                let right = &right[2..];
-
-                let right: Chemical = right.trim().parse()?;
-                let left = left.split(',')
-                    .map(|c| c.trim().parse())
-                    .collect::<Result<Vec<Chemical>, AocErr>>()?;
+        let m = Cursor::new(s)
+            .lines(|c| {
+                let left = c.sep_by(",", chemical)?;
+                c.ws().tag("=>")?;
+                let at = c.ws().rest().trim_end();
+                let right = chemical(c)?;
+
+                //Producing nothing would divide by zero when scaling the reaction
+                if right.num == 0 {
+                    return Err(c.error_at(at, "Reaction produces nothing"));
+                }
 
                 Ok((right.name, (right.num, left)))
-            })
-            .collect::<Result<HashMap<String, (usize, Vec<Chemical>)>, AocErr>>()?;
-
+            })?
+            .into_iter()
+            .collect();
 
         Ok(Data{rules: m})
     }
 }
 
-fn ore_for_fuel(data: &Data) -> usize {
+fn ore_for_fuel(data: &Data) -> AocResult<usize> {
     ore_for_fuel2(data, 1)
 }
 
-fn ore_for_fuel2(data: &Data, fuel: usize) -> usize {
+fn ore_for_fuel2(data: &Data, fuel: usize) -> AocResult<usize> {
     let mut ore = 0;
     let mut excess: HashMap<String, usize> = HashMap::new();
     let mut q = VecDeque::new();
@@ -72,7 +80,7 @@ fn ore_for_fuel2(data: &Data, fuel: usize) -> usize {
 
     while let Some(chem) = q.pop_front() {
         if &chem.name == "ORE" {
-            ore += chem.num;
+            ore = chem.num.checked_add(ore).ok_or_else(|| custom_err("Too much ore"))?;
             continue;
         }
 
@@ -91,8 +99,9 @@ fn ore_for_fuel2(data: &Data, fuel: usize) -> usize {
 
 
         //println!("chem: {}, num: {}", chem.name, num);
-        let rule = &data.rules[&chem.name];
-        let n = ((num as f64) / (rule.0 as f64)).ceil() as usize;
+        let rule = data.rules.get(&chem.name)
+            .ok_or_else(|| custom_err(format!("No rule for {}", chem.name)))?;
+        let n = num.div_ceil(rule.0);
 
         let e = rule.0*n -  num;
         if e > 0 {
@@ -103,37 +112,18 @@ fn ore_for_fuel2(data: &Data, fuel: usize) -> usize {
 
         for prod in rule.1.iter() {
             q.push_back(Chemical{
-                num: prod.num*n,
+                num: prod.num.checked_mul(n).ok_or_else(|| custom_err("Too much ore"))?,
                 name:  prod.name.clone()
             });
         }
     }
 
-    ore
+    Ok(ore)
 }
 
-fn calc_max_fuel(data: &Data, ores: usize) -> usize {
-    let ore_per_fuel = ore_for_fuel(&data);
-    let cur = ores/ore_per_fuel;
-
-    let (mut l, mut r) = (cur, cur*4);
-
-    while l <= r {
-        let m = l + (r-l) / 2;
-        let ore = ore_for_fuel2(&data, m);
-
-        match ore.cmp(&ores) {
-            Ordering::Equal => return m,
-            Ordering::Less if ore_for_fuel2(&data, m+1) > ores => return m,
-            Ordering::Greater => r = m-1,
-            Ordering::Less => l = m+1
-        }
-
-
-    }
-
-    unreachable!()
-
+fn calc_max_fuel(data: &Data, ores: usize) -> AocResult<usize> {
+    let fuel = bisect::try_max_true(0, |fuel| ore_for_fuel2(data, fuel).map(|ore| ore <= ores))?;
+    Ok(fuel.unwrap_or(0))
 }
 
 /*10 ORE => 10 A
@@ -144,38 +134,86 @@ fn calc_max_fuel(data: &Data, ores: usize) -> usize {
 7 A, 1 E => 1 FUEL
 */
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = DAY;
+    type Data = Data;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AocResult<Data> {
+        input.parse()
+    }
+
+    fn part1(data: &Data) -> AocResult<usize> {
+        ore_for_fuel(data)
+    }
+
+    fn part2(data: &Data) -> AocResult<usize> {
+        calc_max_fuel(data, 1_000_000_000_000)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_error() {
+        let err = "10 ORE => 10 A\n7 A, 1x E => 1 FUEL"
+            .parse::<Data>()
+            .err()
+            .unwrap();
+        assert_eq!(
+            "<input>:2:6: Invalid number at `1x`",
+            err.to_string()
+        );
+
+        match err {
+            AocErr::Parse(err) => {
+                assert!(err.render().ends_with("2 | 7 A, 1x E => 1 FUEL\n  |      ^^"))
+            }
+            err => panic!("{}", err),
+        }
+    }
+
     #[test]
     fn part1() -> AocResult<()> {
-        let data: Data = parse_file(FileType::Example, DAY, 01)?;
+        let data: Data = parse_file(FileType::Example, DAY, 1)?;
         let fuel = &data.rules["FUEL"];
         assert_eq!(fuel.0, 1);
-        assert_eq!(ore_for_fuel(&data), 31);
-
-        let data: Data = parse_file(FileType::Example, DAY, 02)?;
-        assert_eq!(ore_for_fuel(&data), 165);
+        assert_eq!(ore_for_fuel(&data)?, 31);
 
-        let data: Data = parse_file(FileType::Example, DAY, 03)?;
-        assert_eq!(ore_for_fuel(&data), 13312);
+        let data: Data = parse_file(FileType::Example, DAY, 2)?;
+        assert_eq!(ore_for_fuel(&data)?, 165);
 
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
-        assert_eq!(ore_for_fuel(&data), 2556890);
+        let data: Data = parse_file(FileType::Example, DAY, 3)?;
+        assert_eq!(ore_for_fuel(&data)?, 13312);
 
-        Ok(())
+        let data: Data = parse_file(FileType::Input, DAY, 1)?;
+        check_answer(FileType::Input, DAY, 1, 1, ore_for_fuel(&data)?)
     }
 
     #[test]
     fn part2() -> AocResult<()> {
         const ORE: usize = 1_000_000_000_000;
 
-        let data: Data = parse_file(FileType::Example, DAY, 03)?;
-        assert_eq!(calc_max_fuel(&data, ORE), 82892753);
+        let data: Data = parse_file(FileType::Example, DAY, 3)?;
+        assert_eq!(calc_max_fuel(&data, ORE)?, 82892753);
 
-        let data: Data = parse_file(FileType::Input, DAY, 01)?;
-        assert_eq!(calc_max_fuel(&data, ORE), 1120408);
-        Ok(())
+        let data: Data = parse_file(FileType::Example, DAY, 1)?;
+        assert_eq!(calc_max_fuel(&data, 0)?, 0);
+        assert_eq!(calc_max_fuel(&data, 30)?, 0);
+        assert_eq!(calc_max_fuel(&data, 31)?, 1);
+
+        let data: Data = "1 A => 1 FUEL".parse()?;
+        assert!(calc_max_fuel(&data, ORE).is_err());
+
+        let err = "10 ORE => 0 A\n1 A => 1 FUEL".parse::<Data>().err().unwrap();
+        assert_eq!("<input>:1:11: Reaction produces nothing at `0 A`", err.to_string());
+
+        let data: Data = parse_file(FileType::Input, DAY, 1)?;
+        check_answer(FileType::Input, DAY, 1, 2, calc_max_fuel(&data, ORE)?)
     }
-}
\ No newline at end of file
+}
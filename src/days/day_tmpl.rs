@@ -1,12 +1,33 @@
 #![allow(dead_code)]
 
+use crate::solution::Solution;
 use crate::*;
-const DAY: usize = 00;
+
+pub struct Day00;
+
+impl Solution for Day00 {
+    const DAY: usize = 0;
+    type Data = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> AocResult<String> {
+        Ok(input.to_string())
+    }
+
+    fn part1(_data: &String) -> AocResult<usize> {
+        Err(custom_err("Not solved yet"))
+    }
+
+    fn part2(_data: &String) -> AocResult<usize> {
+        Err(custom_err("Not solved yet"))
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
- 
+
     #[test]
     fn part1() -> AocResult<()> {
         Ok(())
@@ -16,4 +37,4 @@ mod tests {
     fn part2() -> AocResult<()> {
         Ok(())
     }
-}
\ No newline at end of file
+}
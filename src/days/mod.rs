@@ -11,4 +11,25 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 pub mod day13;
-pub mod day14;
\ No newline at end of file
+pub mod day14;
+
+use crate::solution::Registry;
+
+pub fn registry() -> Registry {
+    let mut r = Registry::default();
+    r.register::<day01::Day01>();
+    r.register::<day02::Day02>();
+    r.register::<day03::Day03>();
+    r.register::<day04::Day04>();
+    r.register::<day05::Day05>();
+    r.register::<day06::Day06>();
+    r.register::<day07::Day07>();
+    r.register::<day08::Day08>();
+    r.register::<day09::Day09>();
+    r.register::<day10::Day10>();
+    r.register::<day11::Day11>();
+    r.register::<day12::Day12>();
+    r.register::<day13::Day13>();
+    r.register::<day14::Day14>();
+    r
+}
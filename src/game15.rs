@@ -1,20 +1,66 @@
 use aoc19::{AocResult, parse_file, FileType};
 use aoc19::days::day05::Data;
-use aoc19::days::day13::{Game, Input};
+use aoc19::days::day13::{Game, Greedy, Input, Strategy};
 use opengl_graphics::{GlGraphics, OpenGL};
-use piston::input::{RenderArgs, RenderEvent, UpdateEvent, UpdateArgs, PressEvent, Button, Key};
+use piston::input::{RenderArgs, RenderEvent, UpdateEvent, UpdateArgs, PressEvent, ReleaseEvent, Button, Key};
 use piston::window::{WindowSettings};
 use glutin_window::GlutinWindow as Window;
 use piston::event_loop::{Events, EventSettings};
-use geo::Point;
+use aoc19::helper::vec::Vec2;
 use itertools::Itertools;
 
+//Seconds per update, the autopilot takes several game ticks per update
+const MANUAL_TICK: f64 = 0.05;
+const AUTO_TICK: f64 = 0.005;
+const AUTO_STEPS: usize = 4;
+
+//Window pixels per game tile and per HUD font pixel
+const TILE: f64 = 12.;
+const FONT_PX: f64 = 3.;
+
+//3x5 pixel glyphs, rows top to bottom separated by spaces
+const FONT: &[(char, &str)] = &[
+    ('0', "### #.# #.# #.# ###"), ('1', ".#. ##. .#. .#. ###"), ('2', "### ..# ### #.. ###"),
+    ('3', "### ..# ### ..# ###"), ('4', "#.# #.# ### ..# ..#"), ('5', "### #.. ### ..# ###"),
+    ('6', "### #.. ### #.# ###"), ('7', "### ..# ..# ..# ..#"), ('8', "### #.# ### #.# ###"),
+    ('9', "### #.# ### ..# ###"), ('A', ".#. #.# ### #.# #.#"), ('B', "##. #.# ##. #.# ##."),
+    ('C', "### #.. #.. #.. ###"), ('E', "### #.. ##. #.. ###"), ('G', "### #.. #.# #.# ###"),
+    ('I', "### .#. .#. .#. ###"), ('K', "#.# #.# ##. #.# #.#"), ('L', "#.. #.. #.. #.. ###"),
+    ('M', "#.# ### ### #.# #.#"), ('N', "##. #.# #.# #.# #.#"), ('O', "### #.# #.# #.# ###"),
+    ('P', "### #.# ### #.. #.."), ('R', "##. #.# ##. #.# #.#"), ('S', "### #.. ### ..# ###"),
+    ('T', "### .#. .#. .#. .#."), ('U', "#.# #.# #.# #.# ###"), ('V', "#.# #.# #.# #.# .#."),
+    ('W', "#.# #.# ### ### #.#"), ('Y', "#.# #.# .#. .#. .#."),
+];
+
+//Pixel squares of `text` with its top left corner at (x, y)
+fn text_rects(text: &str, x: f64, y: f64, px: f64) -> Vec<[f64; 4]> {
+    text.chars()
+        .enumerate()
+        .filter_map(|(ix, c)| FONT.iter().find(|(f, _)| *f == c).map(|(_, g)| (ix, g)))
+        .flat_map(|(ix, glyph)| {
+            glyph.split(' ')
+                .enumerate()
+                .flat_map(move |(gy, row)| {
+                    row.chars()
+                        .enumerate()
+                        .filter(|(_, c)| *c == '#')
+                        .map(move |(gx, _)| {
+                            let gx = (ix * 4 + gx) as f64;
+                            [x + gx * px, y + gy as f64 * px, px, px]
+                        })
+                })
+        })
+        .collect()
+}
 
 pub struct App {
     gl: GlGraphics,
     data: Data,
     game: Game,
     next_input: Input,
+    auto: bool,
+    //A step failed, the game is shown as over until restarted
+    failed: bool,
     dt: f64
 }
 
@@ -22,7 +68,7 @@ impl App {
     fn render(&mut self, args: &RenderArgs) {
         use graphics::*;
 
-        let to_pf = |p: Point<usize>| (p.x() as f64, p.y() as f64);
+        let to_pf = |p: Vec2| (p.x as f64, p.y as f64);
         //let to_tf = |p: (usize, usize)| (p.0 as f64, p.1 as f64);
 
         const BLACK: [f32; 4] = [0., 0., 0., 1.];
@@ -30,6 +76,7 @@ impl App {
         const WHITE: [f32; 4] = [1., 1., 1., 1.];
         const RED: [f32; 4] = [1., 0.0, 0.0, 1.];
         const BLUE: [f32; 4] = [0., 1.0, 0.0, 1.];
+        const SHADE: [f32; 4] = [1., 1., 1., 0.8];
 
 
         //let (w, h) = (args.window_size[0], args.window_size[1]);
@@ -37,13 +84,36 @@ impl App {
        // let zoom = (w / shape.1, h / shape.0);
 
 
-        let p_ball =  to_pf(self.game.ball());
-        let g_ball = rectangle::square(p_ball.0, p_ball.1, 1.);
-
-        let p_paddle = to_pf(self.game.paddle());
-        let g_paddle = rectangle::square(p_paddle.0, p_paddle.1, 1.);
+        let g_ball = self.game.ball()
+            .map(to_pf)
+            .map(|p| rectangle::square(p.0, p.1, 1.));
 
-       // let g_score = self.game.score().to_string();
+        let g_paddle = self.game.paddle()
+            .map(to_pf)
+            .map(|p| rectangle::square(p.0, p.1, 1.));
+
+        let board_h = self.game.shape().1 as f64 * TILE;
+        let blocks = self.game.blocks().count();
+        let mode = if self.auto { "AUTO" } else { "MANUAL" };
+        let mut g_hud = text_rects(&format!("SCORE {}", self.game.score()), 8., board_h + 8., FONT_PX);
+        g_hud.extend(text_rects(&format!("BLOCKS {}", blocks), 200., board_h + 8., FONT_PX));
+        g_hud.extend(text_rects(mode, 400., board_h + 8., FONT_PX));
+
+        let g_over = if self.game.halted() || self.failed {
+            let title = if self.failed {
+                "ERROR"
+            } else if blocks == 0 {
+                "YOU WIN"
+            } else {
+                "GAME OVER"
+            };
+            let mut rects = text_rects(title, 150., board_h / 2. - 40., 2. * FONT_PX);
+            rects.extend(text_rects("PRESS R", 150., board_h / 2. + 10., 2. * FONT_PX));
+            Some(rects)
+        } else {
+            None
+        };
+        let board = [0., 0., self.game.shape().0 as f64 * TILE, board_h];
 
         let g_walls = self.game.walls()
             .map(to_pf)
@@ -59,11 +129,15 @@ impl App {
             clear(WHITE, gl);
 
             let transform = c
-                .scale(12., 12.)
+                .scale(TILE, TILE)
                 .transform;
 
-            ellipse(RED, g_ball, transform, gl);
-            rectangle(BLUE, g_paddle, transform, gl);
+            if let Some(g_ball) = g_ball {
+                ellipse(RED, g_ball, transform, gl);
+            }
+            if let Some(g_paddle) = g_paddle {
+                rectangle(BLUE, g_paddle, transform, gl);
+            }
 
             for wall in g_walls {
                 rectangle(BLACK, wall, transform, gl);
@@ -72,37 +146,72 @@ impl App {
             for block in g_blocks {
                 rectangle(GREY, block, transform, gl);
             }
+
+            for px in g_hud {
+                rectangle(BLACK, px, c.transform, gl);
+            }
+
+            if let Some(g_over) = g_over {
+                rectangle(SHADE, board, c.transform, gl);
+                for px in g_over {
+                    rectangle(RED, px, c.transform, gl);
+                }
+            }
         });
     }
 
+    //Each game step runs the machine until it waits for the next joystick input
     fn update(&mut self, args: &UpdateArgs) {
-        self.dt += args.dt;
-
-        if self.dt >= 0.005 {
-           /* if self.game.update().unwrap() {
-                self.game.set_input(self.next_input);
-                self.next_input = Input::Neutral;
-            }*/
+        if self.game.halted() || self.failed {
+            return;
+        }
 
-            self.game.auto_play().unwrap();
-            self.game.auto_play().unwrap();
-            self.game.auto_play().unwrap();
-            self.game.auto_play().unwrap();
+        self.dt += args.dt;
+        let res = if self.auto && self.dt >= AUTO_TICK {
+            self.auto_steps()
+        } else if !self.auto && self.dt >= MANUAL_TICK {
+            self.game.step(self.next_input).map(|_| ())
+        } else {
+            return;
+        };
+        self.dt = 0.;
+
+        if let Err(err) = res {
+            eprintln!("Game stopped: {}", err);
+            self.failed = true;
+        }
+    }
 
-            self.dt = 0.;
+    //Skips the rest of the tick while the ball or paddle is off screen
+    fn auto_steps(&mut self) -> AocResult<()> {
+        for _ in 0..AUTO_STEPS {
+            let input = match self.game.observe() {
+                Ok(obs) => Greedy.input(&obs),
+                Err(_) => return Ok(()),
+            };
+            self.game.step(input)?;
         }
+        Ok(())
     }
 
     fn reset(&mut self) {
-        self.game = Game::create(self.data.clone(), true)
-            .unwrap();
+        match Game::create(self.data.clone(), true) {
+            Ok(game) => {
+                self.game = game;
+                self.failed = false;
+            }
+            Err(err) => {
+                eprintln!("Restart failed: {}", err);
+                self.failed = true;
+            }
+        }
         self.next_input = Input::Neutral;
-        self.game.set_input(self.next_input);
+        self.dt = 0.;
     }
 }
 
 fn main() -> AocResult<()> {
-    let data: Data = parse_file(FileType::Input, 13, 01)?;
+    let data: Data = parse_file(FileType::Input, 13, 1)?;
     let game = Game::create(data.clone(), true)?;
 
     let opengl = OpenGL::V2_1;
@@ -119,11 +228,11 @@ fn main() -> AocResult<()> {
         data,
         game,
         next_input: Input::Neutral,
+        auto: true,
+        failed: false,
         dt: 0.
     };
 
-    app.game.set_input(Input::Neutral);
-
     let mut events = Events::new(EventSettings::new());
     while let Some(e) = events.next(&mut window) {
         if let Some(e) = e.press_args() {
@@ -131,6 +240,10 @@ fn main() -> AocResult<()> {
                 Button::Keyboard(Key::Left) => Some(Input::Left),
                 Button::Keyboard(Key::Right) => Some(Input::Right),
                 Button::Keyboard(Key::Up) => Some(Input::Neutral),
+                Button::Keyboard(Key::A) => {
+                    app.auto = !app.auto;
+                    None
+                },
                 Button::Keyboard(Key::R) => {
                     app.reset();
                     None
@@ -142,6 +255,18 @@ fn main() -> AocResult<()> {
             }
         }
 
+        //The paddle only moves while an arrow key is held
+        if let Some(e) = e.release_args() {
+            let released = match e {
+                Button::Keyboard(Key::Left) => Some(Input::Left),
+                Button::Keyboard(Key::Right) => Some(Input::Right),
+                _ => None
+            };
+            if released == Some(app.next_input) {
+                app.next_input = Input::Neutral;
+            }
+        }
+
 
         if let Some(args) = e.render_args() {
             app.render(&args);
@@ -0,0 +1,111 @@
+use std::convert::Infallible;
+
+//Largest n >= lo with `pred(n)`, where `pred` holds up to some point and fails after it.
+//Bounds are found by doubling steps first, None if `pred(lo)` already fails
+pub fn try_max_true<E>(
+    lo: usize,
+    mut pred: impl FnMut(usize) -> Result<bool, E>,
+) -> Result<Option<usize>, E> {
+    if !pred(lo)? {
+        return Ok(None);
+    }
+
+    let (mut lo, mut step) = (lo, 1usize);
+    let mut hi = loop {
+        let hi = match lo.checked_add(step) {
+            Some(hi) => hi,
+            None if pred(usize::MAX)? => return Ok(Some(usize::MAX)),
+            None => break usize::MAX,
+        };
+
+        if !pred(hi)? {
+            break hi;
+        }
+
+        lo = hi;
+        step = step.saturating_mul(2);
+    };
+
+    //pred(lo) holds, pred(hi) fails
+    while hi - lo > 1 {
+        let m = lo + (hi - lo) / 2;
+        if pred(m)? {
+            lo = m;
+        } else {
+            hi = m;
+        }
+    }
+
+    Ok(Some(lo))
+}
+
+pub fn max_true(lo: usize, mut pred: impl FnMut(usize) -> bool) -> Option<usize> {
+    unwrap(try_max_true(lo, |n| Ok(pred(n))))
+}
+
+//Smallest n >= lo with `pred(n)`, where `pred` fails up to some point and holds after it.
+//None if it never holds within usize
+pub fn try_min_true<E>(
+    lo: usize,
+    mut pred: impl FnMut(usize) -> Result<bool, E>,
+) -> Result<Option<usize>, E> {
+    if pred(lo)? {
+        return Ok(Some(lo));
+    }
+
+    let last_false = try_max_true(lo, |n| pred(n).map(|b| !b))?;
+    Ok(last_false.and_then(|n| n.checked_add(1)))
+}
+
+pub fn min_true(lo: usize, mut pred: impl FnMut(usize) -> bool) -> Option<usize> {
+    unwrap(try_min_true(lo, |n| Ok(pred(n))))
+}
+
+fn unwrap<T>(res: Result<T, Infallible>) -> T {
+    match res {
+        Ok(t) => t,
+        Err(e) => match e {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_max_true() {
+        assert_eq!(Some(1000), max_true(0, |n| n * n <= 1_000_000));
+        assert_eq!(Some(0), max_true(0, |n| n == 0));
+        assert_eq!(Some(7), max_true(7, |n| n < 8));
+        assert_eq!(None, max_true(5, |n| n < 5));
+        assert_eq!(Some(usize::MAX), max_true(0, |_| true));
+        assert_eq!(Some(usize::MAX - 1), max_true(3, |n| n != usize::MAX));
+
+        assert_eq!(Some(1001), min_true(0, |n| n * n > 1_000_000));
+        assert_eq!(Some(4), min_true(4, |_| true));
+        assert_eq!(None, min_true(0, |_| false));
+    }
+
+    #[test]
+    fn test_fallible() {
+        let mut calls = 0;
+        let res = try_max_true(0, |n| {
+            calls += 1;
+            if n > 100 {
+                Err(custom_err("Too far"))
+            } else {
+                Ok(n < 50)
+            }
+        });
+        assert_eq!(Some(49), res.ok().unwrap());
+        assert!(calls < 20);
+
+        assert!(try_min_true(0, |n| if n > 100 {
+            Err(custom_err("Too far"))
+        } else {
+            Ok(n > 1000)
+        })
+        .is_err());
+    }
+}
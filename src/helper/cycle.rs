@@ -0,0 +1,262 @@
+use num::integer::Integer;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::hash::Hash;
+
+//States from `start` on repeat every `period` steps
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    //Earliest step with the same state as step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    //Cycle of a system made of independent parts
+    pub fn combine(cycles: impl IntoIterator<Item = Cycle>) -> Cycle {
+        cycles.into_iter().fold(
+            Cycle {
+                start: 0,
+                period: 1,
+            },
+            |a, b| Cycle {
+                start: a.start.max(b.start),
+                period: a.period.lcm(&b.period),
+            },
+        )
+    }
+}
+
+pub fn floyd<T: PartialEq>(init: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = f(&init);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = init;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    Cycle { start, period }
+}
+
+pub fn brent<T: PartialEq + Clone>(init: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = init.clone();
+    let mut hare = f(&init);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+
+        hare = f(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = init.clone();
+    let mut hare = init;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+//Walks until a state repeats or step `limit` is reached, keeping the history
+fn walk<T: Eq + Hash + Clone>(
+    init: T,
+    mut f: impl FnMut(&T) -> T,
+    limit: Option<usize>,
+) -> (Option<Cycle>, Vec<T>) {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut cur = init;
+
+    loop {
+        if let Some(&start) = seen.get(&cur) {
+            let period = history.len() - start;
+            return (Some(Cycle { start, period }), history);
+        }
+
+        if limit == Some(history.len()) {
+            history.push(cur);
+            return (None, history);
+        }
+
+        seen.insert(cur.clone(), history.len());
+        let next = f(&cur);
+        history.push(cur);
+        cur = next;
+    }
+}
+
+//Hash based detection, also returns the states of the prefix and one period
+pub fn first_repeat<T: Eq + Hash + Clone>(init: T, f: impl FnMut(&T) -> T) -> (Cycle, Vec<T>) {
+    let (cycle, history) = walk(init, f, None);
+    (cycle.unwrap(), history)
+}
+
+//State after `n` steps, skipping ahead once a cycle is found
+pub fn extrapolate<T: Eq + Hash + Clone>(init: T, f: impl FnMut(&T) -> T, n: usize) -> T {
+    match walk(init, f, Some(n)) {
+        (Some(cycle), mut history) => history.swap_remove(cycle.reduce(n)),
+        (None, mut history) => history.pop().unwrap(),
+    }
+}
+
+//Steps until each of the `count` projections of a simulation is back at its start.
+//Only terminates for reversible simulations where every component cycles from step 0
+pub fn component_periods<T, C: PartialEq>(
+    state: &mut T,
+    mut step: impl FnMut(&mut T),
+    count: usize,
+    mut component: impl FnMut(&T, usize) -> C,
+) -> Vec<usize> {
+    let init: Vec<_> = (0..count).map(|c| component(state, c)).collect();
+    let mut periods = vec![None; count];
+
+    for i in 1.. {
+        step(state);
+
+        for (c, period) in periods.iter_mut().enumerate() {
+            if period.is_none() && component(state, c) == init[c] {
+                *period = Some(i);
+            }
+        }
+
+        if periods.iter().all(|p| p.is_some()) {
+            break;
+        }
+    }
+
+    periods.into_iter().map(|p| p.unwrap()).collect()
+}
+
+pub fn lcm_all(periods: &[usize]) -> usize {
+    periods.iter().fold(1, |acc, p| acc.lcm(p))
+}
+
+//Solves x = r (mod m) for all (r, m), returns (x, lcm of the m) with 0 <= x < lcm.
+//Moduli need not be coprime, None if the congruences contradict each other, a
+//modulus isn't positive or the result doesn't fit in isize
+pub fn crt(congruences: impl IntoIterator<Item = (isize, isize)>) -> Option<(isize, isize)> {
+    congruences
+        .into_iter()
+        .try_fold((0i128, 1i128), |(r1, m1), (r2, m2)| {
+            let (r2, m2) = (r2 as i128, m2 as i128);
+            if m2 <= 0 {
+                return None;
+            }
+
+            let eg = m1.extended_gcd(&m2);
+            if (r2 - r1) % eg.gcd != 0 {
+                return None;
+            }
+
+            let lcm = (m1 / eg.gcd).checked_mul(m2)?;
+            let k = ((r2 - r1) / eg.gcd).checked_mul(eg.x)? % (m2 / eg.gcd);
+            let r = m1.checked_mul(k)?.checked_add(r1)?;
+            Some((r.rem_euclid(lcm), lcm))
+        })
+        .and_then(|(r, m)| Some((isize::try_from(r).ok()?, isize::try_from(m).ok()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //0 1 2 3 4 5 3 4 5 ...
+    fn rho(x: &usize) -> usize {
+        if *x < 5 {
+            x + 1
+        } else {
+            3
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        let expected = Cycle {
+            start: 3,
+            period: 3,
+        };
+        assert_eq!(expected, floyd(0, rho));
+        assert_eq!(expected, brent(0, rho));
+
+        let (cycle, history) = first_repeat(0, rho);
+        assert_eq!(expected, cycle);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], history);
+
+        let lcg = |x: &u64| (x * 75 + 74) % 65537;
+        let (cycle, _) = first_repeat(7, lcg);
+        assert_eq!(cycle, floyd(7, lcg));
+        assert_eq!(cycle, brent(7, lcg));
+
+        assert_eq!(4, extrapolate(0, rho, 1_000_000_000));
+        assert_eq!(2, extrapolate(0, rho, 2));
+        assert_eq!(0, extrapolate(0, rho, 0));
+    }
+
+    #[test]
+    fn test_combine() {
+        let mut state = (0, 0);
+        let step = |s: &mut (usize, usize)| *s = ((s.0 + 1) % 3, (s.1 + 1) % 4);
+        let periods = component_periods(&mut state, step, 2, |s, c| if c == 0 { s.0 } else { s.1 });
+        assert_eq!(vec![3, 4], periods);
+        assert_eq!(12, lcm_all(&periods));
+
+        let cycle = Cycle::combine(vec![
+            Cycle {
+                start: 2,
+                period: 4,
+            },
+            Cycle {
+                start: 5,
+                period: 6,
+            },
+        ]);
+        assert_eq!((5, 12), (cycle.start, cycle.period));
+
+        assert_eq!(Some((23, 105)), crt(vec![(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((9, 12)), crt(vec![(1, 4), (3, 6)]));
+        assert_eq!(None, crt(vec![(0, 4), (1, 6)]));
+        assert_eq!(None, crt(vec![(1, 3), (0, 0)]));
+        assert_eq!(None, crt(vec![(1, -5)]));
+
+        //Coprime moduli whose lcm is far beyond isize
+        let big = vec![(0, isize::MAX), (1, isize::MAX - 1)];
+        assert_eq!(None, crt(big.clone()));
+        assert_eq!(None, crt(big.into_iter().chain(vec![(2, isize::MAX - 2)])));
+    }
+}
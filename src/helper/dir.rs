@@ -1,31 +1,69 @@
-use geo::Point;
+use crate::helper::vec::{Vec2, Vec3};
+use crate::*;
+use std::convert::TryFrom;
+use std::str::FromStr;
 
+//Four way direction on a y-down grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Dir {
     North,
     South,
     West,
-    East
+    East,
+}
+
+//Eight way direction, clockwise starting north
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+//Flat topped hex grid in cube coordinates, x + y + z == 0
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HexDir {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+fn turn_in<T: Copy + PartialEq>(all: &[T], from: T, n: isize) -> T {
+    let ix = all.iter().position(|&d| d == from).unwrap() as isize;
+    all[(ix + n).rem_euclid(all.len() as isize) as usize]
 }
 
 impl Dir {
+    //Clockwise starting north
+    pub const ALL: [Dir; 4] = [Dir::North, Dir::East, Dir::South, Dir::West];
+
+    pub fn all() -> impl Iterator<Item = Dir> {
+        Dir::ALL.iter().cloned()
+    }
+
+    //Quarter turns, positive is clockwise
+    pub fn turn(&self, n: isize) -> Dir {
+        turn_in(&Dir::ALL, *self, n)
+    }
+
     pub fn right(&self) -> Dir {
-        use Dir::*;
-        match *self {
-            North => East,
-            East => South,
-            South => West,
-            West => North
-        }
+        self.turn(1)
     }
 
     pub fn left(&self) -> Dir {
-        use Dir::*;
-        match *self {
-            South => East,
-            West => South,
-            North => West,
-            East => North
-        }
+        self.turn(-1)
+    }
+
+    pub fn opposite(&self) -> Dir {
+        self.turn(2)
     }
 
     pub fn offset(&self) -> (isize, isize) {
@@ -33,19 +71,257 @@ impl Dir {
             Dir::North => (0, -1),
             Dir::East => (1, 0),
             Dir::West => (-1, 0),
-            Dir::South => (0, 1)
+            Dir::South => (0, 1),
+        }
+    }
+
+    pub fn step(&self, pos: Vec2) -> Vec2 {
+        pos + Vec2::from(*self)
+    }
+
+    //Accepts U/D/L/R, N/E/S/W and ^/v/</>
+    pub fn from_char(c: char) -> Option<Dir> {
+        Some(match c {
+            'U' | 'N' | '^' => Dir::North,
+            'D' | 'S' | 'v' => Dir::South,
+            'L' | 'W' | '<' => Dir::West,
+            'R' | 'E' | '>' => Dir::East,
+            _ => return None,
+        })
+    }
+
+    pub fn arrow(&self) -> char {
+        match self {
+            Dir::North => '^',
+            Dir::South => 'v',
+            Dir::West => '<',
+            Dir::East => '>',
+        }
+    }
+
+    //Movement command of the intcode droids, north (1), south (2), west (3), east (4)
+    pub fn to_intcode(&self) -> isize {
+        match self {
+            Dir::North => 1,
+            Dir::South => 2,
+            Dir::West => 3,
+            Dir::East => 4,
+        }
+    }
+
+    pub fn from_intcode(code: isize) -> AocResult<Dir> {
+        Ok(match code {
+            1 => Dir::North,
+            2 => Dir::South,
+            3 => Dir::West,
+            4 => Dir::East,
+            _ => return Err(custom_err(format!("Invalid movement code: {}", code))),
+        })
+    }
+}
+
+impl TryFrom<char> for Dir {
+    type Error = AocErr;
+
+    fn try_from(c: char) -> AocResult<Dir> {
+        Dir::from_char(c).ok_or_else(|| custom_err(format!("Invalid dir: {}", c)))
+    }
+}
+
+impl FromStr for Dir {
+    type Err = AocErr;
+
+    fn from_str(s: &str) -> AocResult<Dir> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Dir::try_from(c),
+            _ => Err(custom_err(format!("Invalid dir: {}", s))),
+        }
+    }
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Dir8> {
+        Dir8::ALL.iter().cloned()
+    }
+
+    //Eighth turns, positive is clockwise
+    pub fn turn(&self, n: isize) -> Dir8 {
+        turn_in(&Dir8::ALL, *self, n)
+    }
+
+    pub fn opposite(&self) -> Dir8 {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, Dir8::NE | Dir8::SE | Dir8::SW | Dir8::NW)
+    }
+
+    pub fn offset(&self) -> Vec2 {
+        let (x, y) = match self {
+            Dir8::N => (0, -1),
+            Dir8::NE => (1, -1),
+            Dir8::E => (1, 0),
+            Dir8::SE => (1, 1),
+            Dir8::S => (0, 1),
+            Dir8::SW => (-1, 1),
+            Dir8::W => (-1, 0),
+            Dir8::NW => (-1, -1),
+        };
+
+        Vec2::new(x, y)
+    }
+
+    pub fn step(&self, pos: Vec2) -> Vec2 {
+        pos + self.offset()
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(dir: Dir) -> Dir8 {
+        match dir {
+            Dir::North => Dir8::N,
+            Dir::East => Dir8::E,
+            Dir::South => Dir8::S,
+            Dir::West => Dir8::W,
         }
     }
+}
+
+impl FromStr for Dir8 {
+    type Err = AocErr;
 
-    pub fn next_pos(&self, pos: Point<usize>) -> Point<usize> {
-        let off = self.offset();
-        offset_pos(pos, off)
+    fn from_str(s: &str) -> AocResult<Dir8> {
+        Ok(match s {
+            "N" => Dir8::N,
+            "NE" => Dir8::NE,
+            "E" => Dir8::E,
+            "SE" => Dir8::SE,
+            "S" => Dir8::S,
+            "SW" => Dir8::SW,
+            "W" => Dir8::W,
+            "NW" => Dir8::NW,
+            _ => return Err(custom_err(format!("Invalid dir: {}", s))),
+        })
     }
 }
 
-pub fn offset_pos(pt: Point<usize>, offset: (isize, isize)) -> Point<usize> {
-    let x = (pt.0.x as isize) +  offset.0;
-    let y = (pt.0.y as isize) +  offset.1;
+impl HexDir {
+    //Clockwise starting north
+    pub const ALL: [HexDir; 6] = [
+        HexDir::N,
+        HexDir::NE,
+        HexDir::SE,
+        HexDir::S,
+        HexDir::SW,
+        HexDir::NW,
+    ];
+
+    pub fn all() -> impl Iterator<Item = HexDir> {
+        HexDir::ALL.iter().cloned()
+    }
+
+    //Sixth turns, positive is clockwise
+    pub fn turn(&self, n: isize) -> HexDir {
+        turn_in(&HexDir::ALL, *self, n)
+    }
+
+    pub fn opposite(&self) -> HexDir {
+        self.turn(3)
+    }
 
-    Point::new(x as usize, y as usize)
-}
\ No newline at end of file
+    pub fn offset(&self) -> Vec3 {
+        let (x, y, z) = match self {
+            HexDir::N => (0, 1, -1),
+            HexDir::NE => (1, 0, -1),
+            HexDir::SE => (1, -1, 0),
+            HexDir::S => (0, -1, 1),
+            HexDir::SW => (-1, 0, 1),
+            HexDir::NW => (-1, 1, 0),
+        };
+
+        Vec3::new(x, y, z)
+    }
+
+    pub fn step(&self, pos: Vec3) -> Vec3 {
+        pos + self.offset()
+    }
+
+    //Number of hex steps between two cube positions
+    pub fn dist(a: Vec3, b: Vec3) -> usize {
+        a.manhattan_dist(b) / 2
+    }
+}
+
+impl FromStr for HexDir {
+    type Err = AocErr;
+
+    fn from_str(s: &str) -> AocResult<HexDir> {
+        Ok(match s {
+            "n" => HexDir::N,
+            "ne" => HexDir::NE,
+            "se" => HexDir::SE,
+            "s" => HexDir::S,
+            "sw" => HexDir::SW,
+            "nw" => HexDir::NW,
+            _ => return Err(custom_err(format!("Invalid hex dir: {}", s))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir() -> AocResult<()> {
+        assert_eq!(Dir::West, Dir::North.left());
+        assert_eq!(Dir::South, Dir::West.turn(-1));
+        assert_eq!(Dir::East, Dir::North.turn(5));
+        assert!(Dir::all().all(|d| d.opposite().opposite() == d));
+        assert!(Dir::all().all(|d| d.step(d.opposite().step(Vec2::ZERO)) == Vec2::ZERO));
+
+        assert_eq!(Dir::West, "L".parse()?);
+        assert_eq!(Dir::North, Dir::try_from('^')?);
+        assert!("X".parse::<Dir>().is_err() && "NE".parse::<Dir>().is_err());
+        assert!(Dir::all().all(|d| Dir::from_char(d.arrow()) == Some(d)));
+        assert_eq!(
+            vec![1, 4, 2, 3],
+            Dir::all().map(|d| d.to_intcode()).collect::<Vec<_>>()
+        );
+        assert!(Dir::all().all(|d| Dir::from_intcode(d.to_intcode()).ok() == Some(d)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dir8_hex() -> AocResult<()> {
+        assert_eq!(Dir8::SW, Dir8::NE.opposite());
+        assert_eq!(Dir8::NW, Dir8::N.turn(-1));
+        assert_eq!(4, Dir8::all().filter(|d| d.is_diagonal()).count());
+        assert_eq!(Dir8::from(Dir::East).offset(), Vec2::from(Dir::East));
+        assert_eq!(Dir8::SE, "SE".parse()?);
+
+        let pos = "ne,ne,s,s"
+            .split(',')
+            .map(|s| s.parse::<HexDir>())
+            .try_fold(Vec3::ZERO, |p, d| Ok::<_, AocErr>(d?.step(p)))?;
+        assert_eq!(2, HexDir::dist(Vec3::ZERO, pos));
+        assert!(HexDir::all().all(|d| d.offset().x + d.offset().y + d.offset().z == 0));
+        assert_eq!(HexDir::S, HexDir::N.opposite());
+
+        Ok(())
+    }
+}
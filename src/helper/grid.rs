@@ -0,0 +1,313 @@
+use crate::helper::vec::Vec2;
+use crate::parse::Cursor;
+use crate::*;
+use image::{ImageBuffer, Pixel};
+use ndarray::{s, Array2};
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+const OFFSETS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const OFFSETS8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+//Indexed by (x, y) with y growing downwards, stored row by row
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    arr: Array2<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            arr: Array2::from_elem((height, width), fill),
+        }
+    }
+
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> AocResult<Grid<T>> {
+        Ok(self.view(x, y, width, height)?.map(|&t| t.clone()))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Grid<T> {
+        Grid {
+            arr: Array2::from_shape_fn((height, width), |(y, x)| f((x, y))),
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> AocResult<Grid<T>> {
+        let width = rows.first().map_or(0, |r| r.len());
+        let height = rows.len();
+        if rows.iter().any(|r| r.len() != width) {
+            return Err(custom_err("Rows of different length"));
+        }
+
+        let v = rows.into_iter().flatten().collect();
+        let arr =
+            Array2::from_shape_vec((height, width), v).map_err(|e| AocErr::Other(Box::new(e)))?;
+        Ok(Grid { arr })
+    }
+
+    //Character map, one row per line, `cell` rejects unknown characters
+    pub fn parse(s: &str, cell: impl FnMut(char) -> Option<T>) -> AocResult<Grid<T>> {
+        Cursor::complete(s, |c| c.grid(cell)).and_then(Self::from_rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.arr.ncols()
+    }
+
+    pub fn height(&self) -> usize {
+        self.arr.nrows()
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width() && y < self.height()
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        self.arr.get((y, x))
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        self.arr.get_mut((y, x))
+    }
+
+    //Signed lookup, None outside the grid
+    pub fn at(&self, pos: Vec2) -> Option<&T> {
+        self.get(pos.to_index()?)
+    }
+
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let (x, y) = (x as isize + dx, y as isize + dy);
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        Some((x as usize, y as usize)).filter(|&pos| self.contains(pos))
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        pos: (usize, usize),
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |&d| self.offset(pos, d))
+    }
+
+    //Up, right, down, left, only positions inside the grid
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(pos, &OFFSETS4)
+    }
+
+    //Clockwise starting upwards, only positions inside the grid
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(pos, &OFFSETS8)
+    }
+
+    //Row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.arr.indexed_iter().map(|((y, x), t)| ((x, y), t))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        self.arr.indexed_iter_mut().map(|((y, x), t)| ((x, y), t))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.arr.iter()
+    }
+
+    pub fn position(&self, mut f: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter().find(|(_, t)| f(t)).map(|(p, _)| p)
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.arr.row(y).into_iter()
+    }
+
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.arr.column(x).into_iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.height()).map(move |y| self.row(y))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width()).map(move |x| self.col(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            arr: self.arr.map(f),
+        }
+    }
+
+    //Borrowing sub grid with its own (0, 0) at (x, y)
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> AocResult<Grid<&T>> {
+        let fits = |start: usize, len: usize, max: usize| {
+            start.checked_add(len).is_some_and(|end| end <= max)
+        };
+        if !fits(x, width, self.width()) || !fits(y, height, self.height()) {
+            return Err(custom_err(format!(
+                "View {}x{} at ({}, {}) exceeds {}x{} grid",
+                width,
+                height,
+                x,
+                y,
+                self.width(),
+                self.height()
+            )));
+        }
+
+        let view = self.arr.slice(s![y..y + height, x..x + width]);
+        let v = view.into_iter().collect();
+        let arr =
+            Array2::from_shape_vec((height, width), v).map_err(|e| AocErr::Other(Box::new(e)))?;
+        Ok(Grid { arr })
+    }
+
+    //Smallest (min, max) corners holding all cells matching `f`
+    pub fn bounds(
+        &self,
+        mut f: impl FnMut(&T) -> bool,
+    ) -> Option<((usize, usize), (usize, usize))> {
+        self.iter()
+            .filter(|(_, t)| f(t))
+            .map(|(p, _)| p)
+            .fold(None, |b, (x, y)| {
+                let ((x0, y0), (x1, y1)) = b.unwrap_or(((x, y), (x, y)));
+                Some(((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y))))
+            })
+    }
+
+    pub fn to_image<P>(&self, mut f: impl FnMut(&T) -> P) -> ImageBuffer<P, Vec<u8>>
+    where
+        P: Pixel<Subpixel = u8> + 'static,
+    {
+        ImageBuffer::from_fn(self.width() as u32, self.height() as u32, |x, y| {
+            f(&self[(x as usize, y as usize)])
+        })
+    }
+
+    pub fn render<D: Display>(&self, mut f: impl FnMut(&T) -> D) -> String {
+        self.rows()
+            .map(|row| row.map(|t| f(t).to_string()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self.arr[(y, x)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        &mut self.arr[(y, x)]
+    }
+}
+
+impl<T> Index<Vec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &T {
+        self.at(pos)
+            .unwrap_or_else(|| panic!("{} outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Vec2> for Grid<T> {
+    fn index_mut(&mut self, pos: Vec2) -> &mut T {
+        pos.to_index()
+            .and_then(move |p| self.get_mut(p))
+            .unwrap_or_else(|| panic!("{} outside of the grid", pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> AocResult<Grid<bool>> {
+        Grid::parse("#..\n.#.\n..#\n#..\n", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_parse() -> AocResult<()> {
+        let grid = sample()?;
+        assert_eq!((3, 4), (grid.width(), grid.height()));
+        assert!(grid[(2, 2)] && grid[(0, 3)] && !grid[(2, 0)]);
+        assert_eq!(None, grid.get((3, 0)));
+        assert!(grid[Vec2::new(1, 1)] && grid.at(Vec2::new(-1, 0)).is_none());
+        assert_eq!(
+            "#..\n.#.\n..#\n#..",
+            grid.render(|&b| if b { '#' } else { '.' })
+        );
+
+        assert!(Grid::parse("#.\n#", Some).is_err());
+        let err = Grid::parse("#.\n#x", |c| if c == '#' { Some(c) } else { None });
+        assert!(err.err().unwrap().to_string().starts_with("<input>:1:2"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbors() -> AocResult<()> {
+        let grid = sample()?;
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbors4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(8, grid.neighbors8((1, 1)).count());
+        assert_eq!(3, grid.neighbors8((2, 3)).count());
+        assert_eq!(None, grid.offset((0, 0), (-1, 0)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rows_and_views() -> AocResult<()> {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(vec![10, 11, 12], grid.row(1).cloned().collect::<Vec<_>>());
+        assert_eq!(vec![2, 12], grid.col(2).cloned().collect::<Vec<_>>());
+        assert_eq!(2, grid.rows().count());
+        assert_eq!(3, grid.cols().count());
+
+        let view = grid.view(1, 1, 2, 1)?;
+        assert_eq!(&12, view[(1, 0)]);
+        assert!(grid.view(2, 0, 2, 1).is_err());
+        assert!(grid.view(1, 0, usize::MAX, 1).is_err());
+        assert!(grid.view(0, usize::MAX, 1, 2).is_err());
+        assert_eq!(
+            vec![11, 12],
+            grid.crop(1, 1, 2, 1)?.values().cloned().collect::<Vec<_>>()
+        );
+
+        assert_eq!(Some(((1, 0), (2, 1))), grid.bounds(|&v| v % 10 > 0));
+        assert_eq!(None, grid.bounds(|&v| v > 100));
+
+        Ok(())
+    }
+}
@@ -1 +1,9 @@
-pub mod dir;
\ No newline at end of file
+pub mod bisect;
+pub mod cycle;
+pub mod dir;
+pub mod grid;
+pub mod ocr;
+pub mod render;
+pub mod search;
+pub mod sparse;
+pub mod vec;
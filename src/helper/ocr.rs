@@ -0,0 +1,146 @@
+use crate::helper::grid::Grid;
+use crate::*;
+
+//Block letters as drawn by the puzzles, side by side with the characters they show
+const FONT_6: (&str, &str) = (
+    "ABCEFGHIJKLOPRSUYZ",
+    "\
+.##..###...##..####.####..##..#..#..###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#...#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####...#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#...#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#...#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#..###..##..#..#.####..##..#....#..#.###...##....#...####",
+);
+
+const FONT_10: (&str, &str) = (
+    "ABCEFGHJKLNPRXZ",
+    "\
+..##....#####....####...######..######...####...#....#.....###..#....#..#.......#....#..#####...#####...#....#..######
+.#..#...#....#..#....#..#.......#.......#....#..#....#......#...#...#...#.......##...#..#....#..#....#..#....#.......#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#..#....#.......##...#..#....#..#....#...#..#........#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#.#.....#.......#.#..#..#....#..#....#...#..#.......#.
+#....#..#####...#.......#####...#####...#.......######......#...##......#.......#.#..#..#####...#####.....##.......#..
+######..#....#..#.......#.......#.......#..###..#....#......#...##......#.......#..#.#..#.......#..#......##......#...
+#....#..#....#..#.......#.......#.......#....#..#....#......#...#.#.....#.......#..#.#..#.......#...#....#..#....#....
+#....#..#....#..#.......#.......#.......#....#..#....#..#...#...#..#....#.......#...##..#.......#...#....#..#...#.....
+#....#..#....#..#....#..#.......#.......#...##..#....#..#...#...#...#...#.......#...##..#.......#....#..#....#..#.....
+#....#..#####....####...######..#........###.#..#....#...###....#....#..######..#....#..#.......#....#..#....#..######",
+);
+
+//Column ranges of the glyphs, which are separated by blank columns
+fn glyph_cols(grid: &Grid<bool>) -> Vec<(usize, usize)> {
+    let lit: Vec<_> = grid.cols().map(|mut col| col.any(|&b| b)).collect();
+    let mut cols = Vec::new();
+    let mut x = 0;
+    while let Some(start) = (x..lit.len()).find(|&x| lit[x]) {
+        let end = (start..lit.len()).find(|&x| !lit[x]).unwrap_or(lit.len());
+        cols.push((start, end));
+        x = end;
+    }
+
+    cols
+}
+
+fn glyph(grid: &Grid<bool>, x0: usize, x1: usize) -> String {
+    (0..grid.height())
+        .map(|y| {
+            (x0..x1)
+                .map(|x| if grid[(x, y)] { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn font((letters, art): (&str, &str)) -> Vec<(char, String)> {
+    let grid = Grid::parse(art, |c| Some(c == '#')).unwrap();
+    letters
+        .chars()
+        .zip(glyph_cols(&grid))
+        .map(|(c, (x0, x1))| (c, glyph(&grid, x0, x1)))
+        .collect()
+}
+
+//Reads lit pixels as block letters
+pub fn read(grid: &Grid<bool>) -> AocResult<String> {
+    let ((x0, y0), (x1, y1)) = grid
+        .bounds(|&b| b)
+        .ok_or_else(|| custom_err("Nothing to read"))?;
+    let grid = grid.crop(x0, y0, x1 - x0 + 1, y1 - y0 + 1)?;
+
+    let font = match grid.height() {
+        6 => font(FONT_6),
+        10 => font(FONT_10),
+        h => return Err(custom_err(format!("No font with height {}", h))),
+    };
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    for (start, end) in glyph_cols(&grid) {
+        let g = glyph(&grid, start, end);
+        match font.iter().find(|(_, pattern)| *pattern == g) {
+            Some(&(c, _)) => text.push(c),
+            None => {
+                text.push('?');
+                unknown.push(format!("{}..{}", x0 + start, x0 + end));
+            }
+        }
+    }
+
+    if !unknown.is_empty() {
+        return Err(custom_err(format!(
+            "Unrecognized glyphs in `{}` at columns {}",
+            text,
+            unknown.join(", ")
+        )));
+    }
+
+    Ok(text)
+}
+
+//'#' is lit, anything else dark
+pub fn read_str(s: &str) -> AocResult<String> {
+    let rows = s
+        .lines()
+        .map(|l| l.chars().map(|c| c == '#').collect())
+        .collect();
+    read(&Grid::from_rows(rows)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() -> AocResult<()> {
+        for &(letters, art) in [FONT_6, FONT_10].iter() {
+            assert_eq!(letters, read_str(art)?);
+        }
+
+        //Offset and uneven gaps
+        let text = "\
+..........
+.#..#..###
+.#..#...#.
+.####...#.
+.#..#...#.
+.#..#...#.
+.#..#..###
+..........";
+        assert_eq!("HI", read_str(text)?);
+
+        let text = "\
+####.#..#.####
+#..#.#..#....#
+#..#.####...#.
+#..#.#..#..#..
+#..#.#..#.#...
+####.#..#.####";
+        let err = read_str(text).err().unwrap();
+        assert!(err.to_string().contains("`?HZ` at columns 0..4"));
+        assert!(read_str("#\n#").is_err() && read_str("...").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+use crate::helper::grid::Grid;
+use crate::*;
+use image::gif::Encoder;
+use image::{Frame, ImageBuffer, Rgb, RgbImage};
+use num::rational::Ratio;
+use std::fs::{self, File};
+use std::path::Path;
+
+//How a cell looks in images (`color`) and in text output (`glyph`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Style {
+    pub color: [u8; 3],
+    pub glyph: char,
+}
+
+impl Style {
+    pub const fn new(color: [u8; 3], glyph: char) -> Style {
+        Style { color, glyph }
+    }
+}
+
+pub const BLACK: Style = Style::new([0, 0, 0], ' ');
+pub const WHITE: Style = Style::new([255, 255, 255], '#');
+
+pub trait Styler<T> {
+    fn style(&self, cell: &T) -> Style;
+}
+
+impl<T, F: Fn(&T) -> Style> Styler<T> for F {
+    fn style(&self, cell: &T) -> Style {
+        self(cell)
+    }
+}
+
+//Fixed mapping of cell values to styles
+#[derive(Clone, Debug)]
+pub struct Palette<T> {
+    entries: Vec<(T, Style)>,
+    default: Style,
+}
+
+impl<T: PartialEq> Palette<T> {
+    pub fn new(default: Style) -> Palette<T> {
+        Palette {
+            entries: Vec::new(),
+            default,
+        }
+    }
+
+    pub fn with(mut self, value: T, style: Style) -> Palette<T> {
+        self.entries.push((value, style));
+        self
+    }
+}
+
+impl<T: PartialEq> Styler<T> for Palette<T> {
+    fn style(&self, cell: &T) -> Style {
+        self.entries
+            .iter()
+            .find(|(v, _)| v == cell)
+            .map_or(self.default, |&(_, s)| s)
+    }
+}
+
+//White on black for pixel grids
+pub fn mono() -> Palette<bool> {
+    Palette::new(BLACK).with(true, WHITE)
+}
+
+fn img_err(e: image::ImageError) -> AocErr {
+    AocErr::Other(Box::new(e))
+}
+
+fn create_parent(path: &Path) -> AocResult<()> {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => Ok(fs::create_dir_all(dir)?),
+        _ => Ok(()),
+    }
+}
+
+//Renders grids with one style per cell, images use `scale` pixels per cell
+pub struct Renderer<S> {
+    styler: S,
+    scale: u32,
+}
+
+impl<S> Renderer<S> {
+    pub fn new(styler: S) -> Renderer<S> {
+        Renderer { styler, scale: 1 }
+    }
+
+    pub fn scale(mut self, scale: u32) -> Renderer<S> {
+        self.scale = scale.max(1);
+        self
+    }
+
+    pub fn image<T>(&self, grid: &Grid<T>) -> RgbImage
+    where
+        S: Styler<T>,
+    {
+        let s = self.scale;
+        let (w, h) = (grid.width() as u32, grid.height() as u32);
+        ImageBuffer::from_fn(w * s, h * s, |x, y| {
+            let cell = &grid[((x / s) as usize, (y / s) as usize)];
+            Rgb(self.styler.style(cell).color)
+        })
+    }
+
+    pub fn save_png<T>(&self, grid: &Grid<T>, path: impl AsRef<Path>) -> AocResult<()>
+    where
+        S: Styler<T>,
+    {
+        let path = path.as_ref();
+        create_parent(path)?;
+        Ok(self.image(grid).save(path)?)
+    }
+
+    //Binary P6 pixmap
+    pub fn ppm<T>(&self, grid: &Grid<T>) -> Vec<u8>
+    where
+        S: Styler<T>,
+    {
+        let img = self.image(grid);
+        let mut v = format!("P6\n{} {}\n255\n", img.width(), img.height()).into_bytes();
+        v.extend(img.into_raw());
+        v
+    }
+
+    pub fn save_ppm<T>(&self, grid: &Grid<T>, path: impl AsRef<Path>) -> AocResult<()>
+    where
+        S: Styler<T>,
+    {
+        let path = path.as_ref();
+        create_parent(path)?;
+        Ok(fs::write(path, self.ppm(grid))?)
+    }
+
+    pub fn ascii<T>(&self, grid: &Grid<T>) -> String
+    where
+        S: Styler<T>,
+    {
+        grid.render(|t| self.styler.style(t).glyph)
+    }
+
+    //Glyphs in 24 bit terminal colors
+    pub fn ansi<T>(&self, grid: &Grid<T>) -> String
+    where
+        S: Styler<T>,
+    {
+        grid.rows()
+            .map(|row| {
+                let mut line = String::new();
+                let mut last = None;
+                for t in row {
+                    let style = self.styler.style(t);
+                    if last != Some(style.color) {
+                        let [r, g, b] = style.color;
+                        line += &format!("\x1b[38;2;{};{};{}m", r, g, b);
+                        last = Some(style.color);
+                    }
+                    line.push(style.glyph);
+                }
+
+                line + "\x1b[0m"
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    //Animation with `delay_ms` per frame, all frames must have the same size
+    pub fn save_gif<'a, T: 'a>(
+        &self,
+        frames: impl IntoIterator<Item = &'a Grid<T>>,
+        delay_ms: u16,
+        path: impl AsRef<Path>,
+    ) -> AocResult<()>
+    where
+        S: Styler<T>,
+    {
+        let path = path.as_ref();
+        let mut size = None;
+        let mut gif_frames = Vec::new();
+        for grid in frames {
+            let shape = (grid.width(), grid.height());
+            let expected = *size.get_or_insert(shape);
+            if expected != shape {
+                return Err(custom_err(format!(
+                    "Frame {} is {}x{}, expected {}x{}",
+                    gif_frames.len(),
+                    shape.0,
+                    shape.1,
+                    expected.0,
+                    expected.1
+                )));
+            }
+
+            let rgba = image::DynamicImage::ImageRgb8(self.image(grid)).to_rgba();
+            //Gif delays are in hundredths of a second
+            let delay = Ratio::from_integer(delay_ms / 10);
+            gif_frames.push(Frame::from_parts(rgba, 0, 0, delay));
+        }
+
+        if gif_frames.is_empty() {
+            return Err(custom_err("No frames"));
+        }
+
+        create_parent(path)?;
+        Encoder::new(File::create(path)?)
+            .encode_frames(gif_frames)
+            .map_err(img_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() -> AocResult<()> {
+        let grid = Grid::parse("#.\n.#\n", |c| Some(c == '#'))?;
+        let r = Renderer::new(mono());
+        assert_eq!("# \n #", r.ascii(&grid));
+        assert_eq!(
+            "\x1b[38;2;255;255;255m#\x1b[38;2;0;0;0m \x1b[0m",
+            r.ansi(&grid).lines().next().unwrap()
+        );
+
+        let digits = Grid::from_fn(3, 1, |(x, _)| x);
+        let r = Renderer::new(Palette::new(BLACK).with(1, Style::new([255, 0, 0], '1')));
+        assert_eq!(" 1 ", r.ascii(&digits));
+        let r = Renderer::new(|&d: &usize| Style::new([d as u8; 3], (b'a' + d as u8) as char));
+        assert_eq!("abc", r.ascii(&digits));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_images() -> AocResult<()> {
+        let grid = Grid::parse("#..\n.#.\n", |c| Some(c == '#'))?;
+        let r = Renderer::new(mono()).scale(3);
+        let img = r.image(&grid);
+        assert_eq!((9, 6), img.dimensions());
+        assert_eq!(Rgb([255; 3]), *img.get_pixel(2, 2));
+        assert_eq!(Rgb([0; 3]), *img.get_pixel(3, 2));
+
+        let ppm = r.ppm(&grid);
+        assert!(ppm.starts_with(b"P6\n9 6\n255\n"));
+        assert_eq!(11 + 9 * 6 * 3, ppm.len());
+
+        let root = std::env::temp_dir().join(format!("aoc19_render_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        r.save_png(&grid, root.join("img/grid.png"))?;
+        r.save_ppm(&grid, root.join("grid.ppm"))?;
+        let flipped = grid.map(|b| !b);
+        r.save_gif(vec![&grid, &flipped], 100, root.join("grid.gif"))?;
+
+        assert_eq!(
+            (9, 6),
+            image::open(root.join("img/grid.png"))
+                .map_err(img_err)?
+                .to_rgb()
+                .dimensions()
+        );
+        assert!(fs::read(root.join("grid.gif"))?.starts_with(b"GIF89a"));
+        assert!(r
+            .save_gif(vec![&grid, &Grid::new(1, 1, true)], 100, root.join("x.gif"))
+            .is_err());
+        fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+}
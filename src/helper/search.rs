@@ -0,0 +1,376 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+//Distances and parent links of every node reached from one start
+#[derive(Clone, Debug)]
+pub struct Reached<N> {
+    dist: HashMap<N, usize>,
+    parent: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash> Reached<N> {
+    pub fn dist(&self, node: &N) -> Option<usize> {
+        self.dist.get(node).cloned()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.dist.contains_key(node)
+    }
+
+    //Start to `to`, both inclusive
+    pub fn path(&self, to: &N) -> Option<Vec<N>> {
+        if !self.contains(to) {
+            return None;
+        }
+
+        Some(reconstruct_path(&self.parent, to.clone()))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&N, usize)> {
+        self.dist.iter().map(|(n, &d)| (n, d))
+    }
+
+    pub fn len(&self) -> usize {
+        self.dist.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dist.is_empty()
+    }
+
+    pub fn max_dist(&self) -> usize {
+        self.dist.values().cloned().max().unwrap_or(0)
+    }
+}
+
+//Follows parent links back to the node without parent, returned start first
+pub fn reconstruct_path<N: Clone + Eq + Hash>(parent: &HashMap<N, N>, to: N) -> Vec<N> {
+    let mut path = vec![to];
+    while let Some(p) = parent.get(path.last().unwrap()) {
+        path.push(p.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+//Breadth first over states that can't be hashed themselves, e.g. intcode snapshots,
+//`key` identifies already visited states. Returns the first goal state and its distance
+pub fn bfs_by_key<S, K, I>(
+    start: S,
+    mut key: impl FnMut(&S) -> K,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<(S, usize)>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::new();
+    let mut q = VecDeque::new();
+    seen.insert(key(&start));
+    q.push_back((start, 0));
+
+    while let Some((state, dist)) = q.pop_front() {
+        if goal(&state) {
+            return Some((state, dist));
+        }
+
+        for next in neighbors(&state) {
+            if seen.insert(key(&next)) {
+                q.push_back((next, dist + 1));
+            }
+        }
+    }
+
+    None
+}
+
+fn bfs_impl<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Reached<N>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached {
+        dist: HashMap::new(),
+        parent: HashMap::new(),
+    };
+    let mut q = VecDeque::new();
+    reached.dist.insert(start.clone(), 0);
+    q.push_back((start, 0));
+
+    while let Some((node, dist)) = q.pop_front() {
+        if goal(&node) {
+            return (reached, Some(node));
+        }
+
+        for next in neighbors(&node) {
+            if !reached.dist.contains_key(&next) {
+                reached.dist.insert(next.clone(), dist + 1);
+                reached.parent.insert(next.clone(), node.clone());
+                q.push_back((next, dist + 1));
+            }
+        }
+    }
+
+    (reached, None)
+}
+
+//Shortest path to the first goal, start and goal inclusive
+pub fn bfs<N, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (reached, found) = bfs_impl(start, neighbors, goal);
+    found.and_then(|n| reached.path(&n))
+}
+
+pub fn bfs_all<N, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Reached<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_impl(start, neighbors, |_| false).0
+}
+
+fn astar_impl<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> usize,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Reached<N>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let mut reached = Reached {
+        dist: HashMap::new(),
+        parent: HashMap::new(),
+    };
+    let mut done = HashSet::new();
+
+    //Nodes live in `nodes`, the heap only orders their indices
+    let mut nodes = vec![start.clone()];
+    let mut heap = BinaryHeap::new();
+    reached.dist.insert(start.clone(), 0);
+    heap.push(Reverse((heuristic(&start), 0, 0)));
+
+    while let Some(Reverse((_, dist, ix))) = heap.pop() {
+        let node = nodes[ix].clone();
+        if reached.dist[&node] < dist || !done.insert(node.clone()) {
+            continue;
+        }
+
+        if goal(&node) {
+            return (reached, Some(node));
+        }
+
+        for (next, cost) in neighbors(&node) {
+            let next_dist = dist + cost;
+            if reached.dist.get(&next).is_some_and(|&d| d <= next_dist) {
+                continue;
+            }
+
+            reached.dist.insert(next.clone(), next_dist);
+            reached.parent.insert(next.clone(), node.clone());
+            heap.push(Reverse((
+                next_dist + heuristic(&next),
+                next_dist,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+
+    (reached, None)
+}
+
+//Cheapest path to the first goal and its cost. Expanded nodes are never reopened, so
+//`heuristic` must be consistent: h(n) <= cost(n, m) + h(m) for every edge n -> m
+pub fn astar<N, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> usize,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, usize)>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    let (reached, found) = astar_impl(start, neighbors, heuristic, goal);
+    let found = found?;
+    Some((reached.path(&found)?, reached.dist(&found)?))
+}
+
+pub fn dijkstra<N, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, usize)>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar(start, neighbors, |_| 0, goal)
+}
+
+pub fn dijkstra_all<N, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Reached<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    astar_impl(start, neighbors, |_| 0, |_| false).0
+}
+
+//Every node reachable from `start`, including it
+pub fn flood_fill<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut stack = vec![start.clone()];
+    seen.insert(start);
+
+    while let Some(node) = stack.pop() {
+        for next in neighbors(&node) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    seen
+}
+
+//Groups `nodes` into connected components, in order of their first node.
+//`neighbors` should be symmetric
+pub fn components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> Vec<HashSet<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut comps = Vec::new();
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+
+        let comp = flood_fill(node, &mut neighbors);
+        seen.extend(comp.iter().cloned());
+        comps.push(comp);
+    }
+
+    comps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::grid::Grid;
+    use crate::*;
+
+    fn maze() -> AocResult<Grid<bool>> {
+        Grid::parse(
+            "S..#....\n.#.#.##.\n.#...#..\n.####.#.\n......#E\n",
+            |c| match c {
+                '#' => Some(false),
+                '.' | 'S' | 'E' => Some(true),
+                _ => None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_bfs() -> AocResult<()> {
+        let grid = maze()?;
+        let open = |&p: &(usize, usize)| grid.neighbors4(p).filter(|&n| grid[n]);
+
+        let path = bfs((0, 0), open, |&p| p == (7, 4)).unwrap();
+        assert_eq!(15, path.len() - 1);
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert!(path
+            .windows(2)
+            .all(|w| grid.neighbors4(w[0]).any(|n| n == w[1])));
+
+        let reached = bfs_all((0, 0), open);
+        assert_eq!(Some(15), reached.dist(&(7, 4)));
+        assert_eq!(grid.values().filter(|&&b| b).count(), reached.len());
+        assert_eq!(None, bfs((0, 0), open, |&p| p == (3, 0)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_weighted() -> AocResult<()> {
+        let grid = &maze()?;
+        //Moving down costs 3
+        let open = |&p: &(usize, usize)| {
+            grid.neighbors4(p)
+                .filter(|&n| grid[n])
+                .map(move |n| (n, if n.1 > p.1 { 3 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let goal = (7, 4);
+        let manhattan = |&(x, y): &(usize, usize)| (goal.0 - x) + (goal.1 - y);
+
+        let (path, cost) = dijkstra((0, 0), open, |&p| p == goal).unwrap();
+        assert_eq!(27, cost);
+        assert_eq!(Some(cost), dijkstra_all((0, 0), open).dist(&goal));
+
+        let (apath, acost) = astar((0, 0), open, manhattan, |&p| p == goal).unwrap();
+        assert_eq!((path.len(), cost), (apath.len(), acost));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill() -> AocResult<()> {
+        let grid = &Grid::parse("..#.\n###.\n.#..\n", |c| Some(c == '.'))?;
+        let same = |&p: &(usize, usize)| {
+            let v = grid[p];
+            grid.neighbors4(p).filter(move |&n| grid[n] == v)
+        };
+
+        assert_eq!(2, flood_fill((0, 0), same).len());
+        let comps = components(grid.iter().map(|(p, _)| p), same);
+        assert_eq!(
+            vec![2, 5, 4, 1],
+            comps.iter().map(|c| c.len()).collect::<Vec<_>>()
+        );
+
+        //Unhashable state, only the value matters
+        let (state, dist) = bfs_by_key(
+            (1, vec![]),
+            |s: &(usize, Vec<char>)| s.0,
+            |s| {
+                let push = |n, c| {
+                    let mut v = s.1.clone();
+                    v.push(c);
+                    (n, v)
+                };
+                vec![push(s.0 * 2, 'd'), push(s.0 + 1, 'i')]
+            },
+            |s| s.0 == 10,
+        )
+        .unwrap();
+        assert_eq!((4, "ddid".to_string()), (dist, state.1.iter().collect()));
+
+        Ok(())
+    }
+}
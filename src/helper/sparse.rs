@@ -0,0 +1,147 @@
+use crate::helper::grid::Grid;
+use crate::helper::vec::Vec2;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Index;
+
+pub type Pos = Vec2;
+
+//Unbounded grid over signed (x, y), cells never written read as the default
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    default: T,
+    bounds: Option<(Pos, Pos)>,
+}
+
+fn extend(bounds: Option<(Pos, Pos)>, p: Pos) -> Option<(Pos, Pos)> {
+    let (min, max) = bounds.unwrap_or((p, p));
+    Some((
+        Vec2::new(min.x.min(p.x), min.y.min(p.y)),
+        Vec2::new(max.x.max(p.x), max.y.max(p.y)),
+    ))
+}
+
+const EMPTY: (Pos, Pos) = (Vec2::new(0, 0), Vec2::new(-1, -1));
+
+impl<T: Clone> SparseGrid<T> {
+    pub fn new(default: T) -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    //Writing a cell, even with the default value, counts it as visited
+    pub fn get_mut(&mut self, pos: Pos) -> &mut T {
+        self.bounds = extend(self.bounds, pos);
+        let default = &self.default;
+        self.cells.entry(pos).or_insert_with(|| default.clone())
+    }
+
+    pub fn set(&mut self, pos: Pos, value: T) {
+        *self.get_mut(pos) = value;
+    }
+
+    pub fn is_set(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(&p, t)| (p, t))
+    }
+
+    //(min, max) corners of all written cells
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        self.bounds
+    }
+
+    pub fn bounds_of(&self, mut f: impl FnMut(&T) -> bool) -> Option<(Pos, Pos)> {
+        self.iter()
+            .filter(|(_, t)| f(t))
+            .fold(None, |b, (p, _)| extend(b, p))
+    }
+
+    //Dense copy of the (inclusive) area, returns the grid and the position of its (0, 0)
+    pub fn to_grid_area(&self, (min, max): (Pos, Pos)) -> (Grid<T>, Pos) {
+        let size = max - min + Vec2::new(1, 1);
+        let width = size.x.max(0) as usize;
+        let height = size.y.max(0) as usize;
+        let grid = Grid::from_fn(width, height, |p| {
+            self.get(min + Vec2::from_index(p)).clone()
+        });
+
+        (grid, min)
+    }
+
+    pub fn to_grid(&self) -> (Grid<T>, Pos) {
+        self.to_grid_area(self.bounds.unwrap_or(EMPTY))
+    }
+
+    //Dense grid cropped to the cells matching `f`
+    pub fn crop(&self, f: impl FnMut(&T) -> bool) -> (Grid<T>, Pos) {
+        self.to_grid_area(self.bounds_of(f).unwrap_or(EMPTY))
+    }
+
+    pub fn render<D: Display>(&self, f: impl FnMut(&T) -> D) -> String {
+        self.to_grid().0.render(f)
+    }
+}
+
+impl<T: Clone> Index<Pos> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(None, grid.bounds());
+        assert_eq!(
+            (0, 0),
+            (grid.to_grid().0.width(), grid.to_grid().0.height())
+        );
+
+        grid.set(Vec2::new(-300, 2), '#');
+        grid.set(Vec2::new(1, -1), '#');
+        *grid.get_mut(Vec2::ZERO) = '.';
+
+        assert_eq!('#', grid[Vec2::new(-300, 2)]);
+        assert_eq!('.', grid[Vec2::new(1000, 1000)]);
+        assert_eq!(3, grid.len());
+        assert!(grid.is_set(Vec2::ZERO) && !grid.is_set(Vec2::new(5, 5)));
+        assert_eq!(Some((Vec2::new(-300, -1), Vec2::new(1, 2))), grid.bounds());
+
+        let (dense, origin) = grid.to_grid();
+        assert_eq!(Vec2::new(-300, -1), origin);
+        assert_eq!((302, 4), (dense.width(), dense.height()));
+        assert_eq!('#', dense[(0, 3)]);
+
+        grid.set(Vec2::new(-300, 2), '.');
+        let (cropped, origin) = grid.crop(|&c| c == '#');
+        assert_eq!(
+            (Vec2::new(1, -1), "#"),
+            (origin, cropped.render(|&c| c).as_str())
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use crate::helper::dir::Dir;
+use crate::*;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+//Screen coordinates, y grows downwards so `Dir::North` is (0, -1)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn from_index((x, y): (usize, usize)) -> Vec2 {
+        Vec2::new(x as isize, y as isize)
+    }
+
+    //None for negative coordinates
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    pub fn manhattan(self) -> usize {
+        (self.x.abs() + self.y.abs()) as usize
+    }
+
+    pub fn chebyshev(self) -> usize {
+        self.x.abs().max(self.y.abs()) as usize
+    }
+
+    pub fn manhattan_dist(self, other: Vec2) -> usize {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev_dist(self, other: Vec2) -> usize {
+        (other - self).chebyshev()
+    }
+
+    pub fn signum(self) -> Vec2 {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    //Clockwise on screen
+    pub fn rotate_right(self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    pub fn rotate_left(self) -> Vec2 {
+        Vec2::new(self.y, -self.x)
+    }
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    pub fn manhattan(self) -> usize {
+        (self.x.abs() + self.y.abs() + self.z.abs()) as usize
+    }
+
+    pub fn chebyshev(self) -> usize {
+        self.x.abs().max(self.y.abs()).max(self.z.abs()) as usize
+    }
+
+    pub fn manhattan_dist(self, other: Vec3) -> usize {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev_dist(self, other: Vec3) -> usize {
+        (other - self).chebyshev()
+    }
+
+    pub fn signum(self) -> Vec3 {
+        Vec3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+macro_rules! impl_ops {
+    ($t:ident, $($f:ident),+) => {
+        impl Add for $t {
+            type Output = $t;
+
+            fn add(self, o: $t) -> $t {
+                $t { $($f: self.$f + o.$f),+ }
+            }
+        }
+
+        impl Sub for $t {
+            type Output = $t;
+
+            fn sub(self, o: $t) -> $t {
+                $t { $($f: self.$f - o.$f),+ }
+            }
+        }
+
+        impl Mul<isize> for $t {
+            type Output = $t;
+
+            fn mul(self, k: isize) -> $t {
+                $t { $($f: self.$f * k),+ }
+            }
+        }
+
+        impl Neg for $t {
+            type Output = $t;
+
+            fn neg(self) -> $t {
+                $t { $($f: -self.$f),+ }
+            }
+        }
+
+        impl AddAssign for $t {
+            fn add_assign(&mut self, o: $t) {
+                *self = *self + o;
+            }
+        }
+
+        impl SubAssign for $t {
+            fn sub_assign(&mut self, o: $t) {
+                *self = *self - o;
+            }
+        }
+    };
+}
+
+impl_ops!(Vec2, x, y);
+impl_ops!(Vec3, x, y, z);
+
+//Axis access, 0 = x, 1 = y, 2 = z
+impl Index<usize> for Vec3 {
+    type Output = isize;
+
+    fn index(&self, axis: usize) -> &isize {
+        match axis {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Invalid axis {}", axis),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec3 {
+    fn index_mut(&mut self, axis: usize) -> &mut isize {
+        match axis {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Invalid axis {}", axis),
+        }
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((x, y): (isize, isize)) -> Vec2 {
+        Vec2::new(x, y)
+    }
+}
+
+impl From<Vec2> for (isize, isize) {
+    fn from(v: Vec2) -> (isize, isize) {
+        (v.x, v.y)
+    }
+}
+
+impl From<(isize, isize, isize)> for Vec3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Vec3 {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl From<Dir> for Vec2 {
+    fn from(dir: Dir) -> Vec2 {
+        dir.offset().into()
+    }
+}
+
+impl TryFrom<Vec2> for Dir {
+    type Error = AocErr;
+
+    fn try_from(v: Vec2) -> AocResult<Dir> {
+        Ok(match (v.x, v.y) {
+            (0, -1) => Dir::North,
+            (1, 0) => Dir::East,
+            (0, 1) => Dir::South,
+            (-1, 0) => Dir::West,
+            _ => return Err(custom_err(format!("{} is not a unit step", v))),
+        })
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vec2() -> AocResult<()> {
+        let (a, b) = (Vec2::new(1, -2), Vec2::new(-3, 4));
+        assert_eq!(Vec2::new(-2, 2), a + b);
+        assert_eq!(Vec2::new(4, -6), a - b);
+        assert_eq!(Vec2::new(3, -6), a * 3);
+        assert_eq!(10, a.manhattan_dist(b));
+        assert_eq!(6, a.chebyshev_dist(b));
+        assert_eq!(None, b.to_index());
+        assert_eq!(Some((3, 4)), Vec2::new(3, 4).to_index());
+
+        let north: Vec2 = Dir::North.into();
+        assert_eq!(Vec2::from(Dir::East), north.rotate_right());
+        assert_eq!(Vec2::from(Dir::West), north.rotate_left());
+        assert_eq!(
+            north,
+            north
+                .rotate_right()
+                .rotate_right()
+                .rotate_left()
+                .rotate_left()
+        );
+        assert!(matches!(Dir::try_from(-north)?, Dir::South));
+        assert!(Dir::try_from(a).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vec3() {
+        let mut v = Vec3::new(2, -3, 0);
+        v += Vec3::new(1, 1, 1);
+        assert_eq!(Vec3::new(3, -2, 1), v);
+        assert_eq!(6, v.manhattan());
+        assert_eq!(3, v.chebyshev());
+        assert_eq!(Vec3::new(1, -1, 1), v.signum());
+        assert_eq!(-2, v[1]);
+    }
+}
@@ -0,0 +1,456 @@
+use crate::solution::Registry;
+use crate::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub struct Source {
+    pub name: String,
+    pub contents: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+pub struct Options {
+    pub dry_run: bool,
+    pub force: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Written,
+    Unchanged,
+    Conflict,
+    Rejected(String),
+    Skipped(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Written => f.write_str("written"),
+            Status::Unchanged => f.write_str("unchanged"),
+            Status::Conflict => f.write_str("conflict, differs from existing file"),
+            Status::Rejected(why) => write!(f, "rejected: {}", why),
+            Status::Skipped(why) => write!(f, "skipped: {}", why),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Entry {
+    pub source: String,
+    pub file_type: Option<FileType>,
+    pub path: Option<PathBuf>,
+    pub status: Status,
+}
+
+impl Entry {
+    pub fn is_error(&self) -> bool {
+        match self.status {
+            Status::Conflict => true,
+            Status::Rejected(_) => self.file_type == Some(FileType::Input),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.source, self.status)?;
+        if let Some(p) = &self.path {
+            write!(f, " ({})", p.display())?;
+        }
+        Ok(())
+    }
+}
+
+fn read_dir_into(root: &Path, dir: &Path, out: &mut Vec<Source>) -> AocResult<()> {
+    for entry in dir.read_dir()? {
+        let path = entry?.path();
+        if path.is_dir() {
+            read_dir_into(root, &path, out)?;
+        } else {
+            out.push(Source {
+                name: path
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned(),
+                contents: fs::read(&path)?,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn read_dir(p: &Path) -> AocResult<Vec<Source>> {
+    let mut sources = Vec::new();
+    read_dir_into(p, p, &mut sources)?;
+    sources.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(sources)
+}
+
+pub fn read_archive<R: Read>(r: R) -> AocResult<Vec<Source>> {
+    let mut sources = Vec::new();
+    let mut archive = tar::Archive::new(r);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        sources.push(Source { name, contents });
+    }
+
+    sources.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(sources)
+}
+
+//A directory or a plain `.tar` archive
+pub fn read_sources(p: &Path) -> AocResult<Vec<Source>> {
+    if p.is_dir() {
+        read_dir(p)
+    } else {
+        read_archive(fs::File::open(p)?)
+    }
+}
+
+//Same shape as the files in `data/`: `\n` line endings, no trailing whitespace or final newline
+pub fn normalize(s: &str) -> String {
+    s.lines()
+        .map(|l| l.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+//Innermost path component that holds a day number, e.g. `2019/day07.html` or `7/input`
+pub fn day_of(name: &str) -> Option<usize> {
+    Path::new(name)
+        .iter()
+        .rev()
+        .find_map(|c| component_day(c.to_str()?))
+}
+
+//`day` or `d` starting a word, optionally followed by `_` or `-`
+fn is_day_marker(prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches(['_', '-']);
+    ["day", "d"].iter().any(|m| match prefix.strip_suffix(m) {
+        Some(before) => !before.ends_with(|c: char| c.is_ascii_alphabetic()),
+        None => false,
+    })
+}
+
+//A number after a day marker wins over others, so `2019_day07` is day 7
+fn component_day(c: &str) -> Option<usize> {
+    let c = c.find('.').map_or(c, |ix| &c[..ix]).to_ascii_lowercase();
+
+    let mut numbers = Vec::new();
+    let mut rest = c.as_str();
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        let len = rest[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - start);
+        numbers.push((is_day_marker(&rest[..start]), &rest[start..start + len]));
+        rest = &rest[start + len..];
+    }
+
+    let marked = numbers.iter().filter(|(marked, _)| *marked);
+    let unmarked = numbers.iter().filter(|(marked, _)| !*marked);
+    marked
+        .chain(unmarked)
+        .filter_map(|(_, n)| n.parse().ok())
+        .find(|day| (1..=25).contains(day))
+}
+
+fn is_html(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.ends_with(".html") || name.ends_with(".htm")
+}
+
+fn strip_tags(s: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+//Contents of every distinct `<pre><code>` block in a saved puzzle page
+pub fn extract_examples(html: &str) -> Vec<String> {
+    const OPEN: &str = "<pre><code>";
+    const CLOSE: &str = "</code></pre>";
+
+    let mut examples = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(OPEN) {
+        rest = &rest[start + OPEN.len()..];
+        let end = match rest.find(CLOSE) {
+            Some(end) => end,
+            None => break,
+        };
+
+        let block = normalize(&unescape(&strip_tags(&rest[..end])));
+        if !block.is_empty() && !examples.contains(&block) {
+            examples.push(block);
+        }
+        rest = &rest[end + CLOSE.len()..];
+    }
+
+    examples
+}
+
+struct Importer<'a> {
+    profile: &'a Profile,
+    registry: &'a Registry,
+    opts: &'a Options,
+    //Files placed by this run, needed to number examples during a dry run
+    placed: HashMap<PathBuf, String>,
+    entries: Vec<Entry>,
+}
+
+impl<'a> Importer<'a> {
+    fn existing(&self, p: &Path) -> AocResult<Option<String>> {
+        if let Some(s) = self.placed.get(p) {
+            return Ok(Some(s.clone()));
+        }
+
+        match fs::read_to_string(p) {
+            Ok(s) => Ok(Some(normalize(&s))),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn place(&mut self, p: PathBuf, text: String) -> AocResult<Status> {
+        if !self.opts.dry_run {
+            fs::create_dir_all(p.parent().unwrap())?;
+            fs::write(&p, &text)?;
+        }
+
+        self.placed.insert(p, text);
+        Ok(Status::Written)
+    }
+
+    fn validate(&self, day: usize, text: &str) -> Option<Status> {
+        if text.is_empty() {
+            return Some(Status::Rejected("empty".to_string()));
+        }
+
+        let solution = self.registry.get(day)?;
+        match solution.parse(text) {
+            Ok(_) => None,
+            Err(err) => Some(Status::Rejected(format!("does not parse: {}", err))),
+        }
+    }
+
+    fn push(
+        &mut self,
+        source: String,
+        file_type: Option<FileType>,
+        path: Option<PathBuf>,
+        status: Status,
+    ) {
+        self.entries.push(Entry {
+            source,
+            file_type,
+            path,
+            status,
+        });
+    }
+
+    fn input(&mut self, source: String, day: usize, text: String) -> AocResult<()> {
+        let p = self.profile.file_path(FileType::Input, day, 1);
+        let status = match self.validate(day, &text) {
+            Some(rejected) => rejected,
+            None => match self.existing(&p)? {
+                Some(old) if old == text => Status::Unchanged,
+                Some(_) if !self.opts.force => Status::Conflict,
+                _ => self.place(p.clone(), text)?,
+            },
+        };
+
+        self.push(source, Some(FileType::Input), Some(p), status);
+        Ok(())
+    }
+
+    fn example(&mut self, source: String, day: usize, text: String) -> AocResult<()> {
+        if let Some(rejected) = self.validate(day, &text) {
+            self.push(source, Some(FileType::Example), None, rejected);
+            return Ok(());
+        }
+
+        //Reuse a file with the same content, otherwise take the first free number
+        for task in 1.. {
+            let p = self.profile.file_path(FileType::Example, day, task);
+            let status = match self.existing(&p)? {
+                Some(old) if old == text => Status::Unchanged,
+                Some(_) => continue,
+                None => self.place(p.clone(), text)?,
+            };
+
+            self.push(source, Some(FileType::Example), Some(p), status);
+            break;
+        }
+
+        Ok(())
+    }
+
+    fn source(&mut self, source: &Source) -> AocResult<()> {
+        let name = source.name.clone();
+        let day = match day_of(&name) {
+            Some(day) if self.registry.get(day).is_some() => day,
+            Some(day) => {
+                let why = format!("no solution for day {}", day);
+                self.push(name, None, None, Status::Skipped(why));
+                return Ok(());
+            }
+            None => {
+                let why = "no day number in name".to_string();
+                self.push(name, None, None, Status::Skipped(why));
+                return Ok(());
+            }
+        };
+
+        let text = match std::str::from_utf8(&source.contents) {
+            Ok(text) => text,
+            Err(_) => {
+                let why = "not UTF-8".to_string();
+                self.push(name, None, None, Status::Rejected(why));
+                return Ok(());
+            }
+        };
+
+        if is_html(&name) {
+            for (ix, example) in extract_examples(text).into_iter().enumerate() {
+                self.example(format!("{}#{}", name, ix + 1), day, example)?;
+            }
+            Ok(())
+        } else {
+            self.input(name, day, normalize(text))
+        }
+    }
+}
+
+//Puzzle pages (`.html`) provide examples, every other file is taken as the day's input
+pub fn import(
+    sources: &[Source],
+    profile: &Profile,
+    registry: &Registry,
+    opts: &Options,
+) -> AocResult<Vec<Entry>> {
+    let mut importer = Importer {
+        profile,
+        registry,
+        opts,
+        placed: HashMap::new(),
+        entries: Vec::new(),
+    };
+
+    for source in sources {
+        importer.source(source)?;
+    }
+
+    Ok(importer.entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::registry;
+
+    fn source(name: &str, contents: &str) -> Source {
+        Source {
+            name: name.to_string(),
+            contents: contents.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!("12\n14", normalize("12 \r\n14\t\r\n\r\n"));
+        assert_eq!(Some(7), day_of("2019/day07.html"));
+        assert_eq!(Some(12), day_of("12/input"));
+        assert_eq!(None, day_of("2019/readme.txt"));
+        assert_eq!(Some(7), day_of("2019_day07.txt"));
+        assert_eq!(Some(7), day_of("aoc2019/2019-d7.txt"));
+        assert_eq!(Some(3), day_of("2019_12/Day_03/input.txt"));
+        assert_eq!(Some(7), day_of("2019_07.txt"));
+
+        let html = "<p>x</p><pre><code>R8,U5\n<em>U7</em>,L6\n</code></pre>\
+                    <pre><code>a &lt;- b &amp;&amp; c</code></pre><pre><code>R8,U5\nU7,L6</code></pre>";
+        assert_eq!(
+            vec!["R8,U5\nU7,L6".to_string(), "a <- b && c".to_string()],
+            extract_examples(html)
+        );
+    }
+
+    #[test]
+    fn test_import() -> AocResult<()> {
+        let root = std::env::temp_dir().join(format!("aoc19_import_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let profile = Profile::with_root(root.clone(), Some("test"));
+        let registry = registry();
+
+        let page =
+            "<pre><code>R8,U5,L5,D3\nU7,R6,D4,L4</code></pre><pre><code>not a wire</code></pre>";
+        let mut archive = tar::Builder::new(Vec::new());
+        for (name, contents) in [("day01.txt", "12\r\n14  \n\n"), ("day03.html", page)].iter() {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            archive.append_data(&mut header, name, contents.as_bytes())?;
+        }
+        let mut sources = read_archive(&archive.into_inner()?[..])?;
+        sources.push(source("day02.txt", "1,2,x"));
+        sources.push(source("notes.txt", "hello"));
+
+        let entries = import(&sources, &profile, &registry, &Options::default())?;
+        let status: Vec<_> = entries.iter().map(|e| &e.status).collect();
+        assert_eq!(5, status.len());
+        assert_eq!(&Status::Written, status[0]);
+        assert_eq!(&Status::Written, status[1]);
+        assert!(matches!(status[2], Status::Rejected(_)));
+        assert!(entries[3].is_error());
+        assert!(matches!(status[4], Status::Skipped(_)));
+
+        assert_eq!("12\n14", profile.read_file(FileType::Input, 1, 1)?);
+        assert_eq!(
+            "R8,U5,L5,D3\nU7,R6,D4,L4",
+            profile.read_file(FileType::Example, 3, 1)?
+        );
+
+        let again = import(&sources[..2], &profile, &registry, &Options::default())?;
+        assert!(again.iter().all(|e| e.status != Status::Written));
+        assert_eq!(Status::Unchanged, again[1].status);
+
+        let changed = [source("01.txt", "15")];
+        let conflict = import(&changed, &profile, &registry, &Options::default())?;
+        assert_eq!(Status::Conflict, conflict[0].status);
+
+        fs::remove_dir_all(root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use aoc19::days::registry;
+use aoc19::import::{import, read_sources, Options, Status};
+use aoc19::{custom_err, AocResult, Profile};
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: import <dir | archive.tar> [--profile NAME] [--dry-run] [--force]";
+
+fn main() -> AocResult<()> {
+    let mut opts = Options::default();
+    let mut profile = Profile::current();
+    let mut source = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => opts.dry_run = true,
+            "--force" => opts.force = true,
+            "--profile" => {
+                let name = args.next().ok_or_else(|| custom_err(USAGE))?;
+                profile = Profile::named(Some(name.as_str()).filter(|n| *n != "default"));
+            }
+            a if a.starts_with("--") => return Err(custom_err(USAGE)),
+            _ if source.is_none() => source = Some(PathBuf::from(arg)),
+            _ => return Err(custom_err(USAGE)),
+        }
+    }
+
+    let source = source.ok_or_else(|| custom_err(USAGE))?;
+    let entries = import(&read_sources(&source)?, &profile, &registry(), &opts)?;
+
+    for entry in entries.iter() {
+        println!("{}", entry);
+    }
+
+    let written = entries
+        .iter()
+        .filter(|e| e.status == Status::Written)
+        .count();
+    let errors = entries.iter().filter(|e| e.is_error()).count();
+    println!(
+        "{} files {}, {} errors",
+        written,
+        if opts.dry_run {
+            "would be written"
+        } else {
+            "written"
+        },
+        errors
+    );
+
+    if errors > 0 {
+        return Err(custom_err(format!("{} files not imported", errors)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::days::day05::{decode, Opcode, Value};
+use std::fmt;
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Position(ix) => write!(f, "[{}]", ix),
+            Value::Immediate(n) => write!(f, "{}", n),
+            Value::Relative(off) if *off < 0 => write!(f, "[rb-{}]", -off),
+            Value::Relative(off) => write!(f, "[rb+{}]", off),
+        }
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Opcode::Add(a, b, c) => write!(f, "add {}, {}, {}", a, b, c),
+            Opcode::Mul(a, b, c) => write!(f, "mul {}, {}, {}", a, b, c),
+            Opcode::In(a) => write!(f, "in {}", a),
+            Opcode::Out(a) => write!(f, "out {}", a),
+            Opcode::JumpTrue(a, b) => write!(f, "jt {}, {}", a, b),
+            Opcode::JumpFalse(a, b) => write!(f, "jf {}, {}", a, b),
+            Opcode::CmpLt(a, b, c) => write!(f, "lt {}, {}, {}", a, b, c),
+            Opcode::CmpEq(a, b, c) => write!(f, "eq {}, {}, {}", a, b, c),
+            Opcode::Halt => write!(f, "halt"),
+            Opcode::SetBase(a) => write!(f, "rb {}", a),
+        }
+    }
+}
+
+pub struct Line {
+    pub addr: usize,
+    pub len: usize,
+    pub text: String,
+}
+
+//Linear sweep, cells that don't decode are emitted as data
+pub fn disassemble(mem: &[isize]) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut addr = 0;
+
+    while addr < mem.len() {
+        let mut window = [0; 4];
+        let avail = (mem.len() - addr).min(4);
+        window[..avail].copy_from_slice(&mem[addr..addr + avail]);
+
+        let line = match decode(&window) {
+            Ok((op, len)) if len <= avail => Line {
+                addr,
+                len,
+                text: op.to_string(),
+            },
+            _ => Line {
+                addr,
+                len: 1,
+                text: format!(".data {}", mem[addr]),
+            },
+        };
+
+        addr += line.len;
+        lines.push(line);
+    }
+
+    lines
+}
+
+pub fn opcode_name(cell: isize) -> Option<&'static str> {
+    Some(match cell % 100 {
+        1 => "add",
+        2 => "mul",
+        3 => "in",
+        4 => "out",
+        5 => "jt",
+        6 => "jf",
+        7 => "lt",
+        8 => "eq",
+        9 => "rb",
+        99 => "halt",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dis() {
+        let lines = disassemble(&[1002, 4, 3, 4, 33, 109, -1, 21101, 1, 2, 3, 99, 7]);
+        let text: Vec<_> = lines.iter().map(|l| (l.addr, l.text.as_str())).collect();
+
+        assert_eq!(
+            text,
+            vec![
+                (0, "mul [4], 3, [4]"),
+                (4, ".data 33"),
+                (5, "rb -1"),
+                (7, "add 1, 2, [rb+3]"),
+                (11, "halt"),
+                (12, ".data 7"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,395 @@
+use crate::days::day05::{Context, Data};
+use crate::*;
+use std::fs;
+use std::path::Path;
+
+pub const MAGIC: &[u8; 4] = b"ICIM";
+pub const VERSION: u8 = 1;
+const HEADER_LEN: usize = 14;
+const FLAG_STATE: u8 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Binary,
+}
+
+impl Format {
+    pub fn from_path(p: &Path) -> Format {
+        match p.extension().and_then(|e| e.to_str()) {
+            Some("icb") | Some("bin") => Format::Binary,
+            _ => Format::Text,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    pub pc: usize,
+    pub base: isize,
+    pub halted: bool,
+    pub input: Vec<isize>,
+    pub output: Vec<isize>,
+}
+
+impl State {
+    //A resumed machine decodes at `pc` right away. `base` is only ever used with an
+    //offset, so any value is valid
+    fn check(&self, len: usize) -> AocResult<()> {
+        //A halted machine has already moved past its last instruction
+        if self.pc > len || (self.pc == len && !self.halted) {
+            return Err(custom_err(format!("Image pc {} out of bounds", self.pc)));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub memory: Vec<isize>,
+    pub state: Option<State>,
+}
+
+//Accepts whitespace, `#` comments and a trailing comma
+pub fn parse_text(s: &str) -> AocResult<Vec<isize>> {
+    let s = s
+        .lines()
+        .map(|l| l.find('#').map_or(l, |ix| &l[..ix]))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut tokens: Vec<&str> = s.split(',').map(|t| t.trim()).collect();
+    if tokens.last() == Some(&"") {
+        tokens.pop();
+    }
+
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(ix, t)| match t {
+            &"" => Err(custom_err(format!("Empty value at position {}", ix))),
+            t => t.parse().map_err(AocErr::from),
+        })
+        .collect()
+}
+
+pub fn checksum(bytes: &[u8]) -> u32 {
+    //FNV-1a
+    bytes.iter().fold(0x811c_9dc5, |h: u32, &b| {
+        (h ^ b as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+fn write_var(out: &mut Vec<u8>, n: isize) {
+    //Zigzag LEB128
+    let mut n = ((n << 1) ^ (n >> 63)) as u64;
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_var(bytes: &[u8], pos: &mut usize) -> AocResult<isize> {
+    let mut n = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes
+            .get(*pos)
+            .ok_or_else(|| custom_err("Unexpected end of image"))?;
+        *pos += 1;
+        n |= ((byte & 0x7f) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(((n >> 1) as isize) ^ -((n & 1) as isize));
+        }
+    }
+
+    Err(custom_err("Varint too long"))
+}
+
+fn read_vec(bytes: &[u8], pos: &mut usize) -> AocResult<Vec<isize>> {
+    let len = read_var(bytes, pos)?;
+    (0..len).map(|_| read_var(bytes, pos)).collect()
+}
+
+impl Image {
+    pub fn from_data(data: Data) -> Image {
+        Image {
+            memory: data.0,
+            state: None,
+        }
+    }
+
+    pub fn from_context(ctx: &Context) -> Image {
+        Image {
+            memory: ctx.data().to_vec(),
+            state: Some(State {
+                pc: ctx.pc(),
+                base: ctx.base(),
+                halted: ctx.halted(),
+                input: ctx.inputs().collect(),
+                output: ctx.outputs().to_vec(),
+            }),
+        }
+    }
+
+    pub fn into_data(self) -> Data {
+        Data(self.memory)
+    }
+
+    pub fn into_context(self) -> AocResult<Context> {
+        let data = Data(self.memory);
+        Ok(match self.state {
+            Some(state) => {
+                state.check(data.0.len())?;
+                Context::from_state(
+                    data,
+                    state.pc,
+                    state.base,
+                    state.halted,
+                    &state.input,
+                    state.output,
+                )
+            }
+            None => Context::from_data(data, &[]),
+        })
+    }
+
+    fn body(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        for &n in self.memory.iter() {
+            write_var(&mut body, n);
+        }
+
+        if let Some(state) = &self.state {
+            write_var(&mut body, state.pc as isize);
+            write_var(&mut body, state.base);
+            write_var(&mut body, state.halted as isize);
+            for v in [&state.input, &state.output].iter() {
+                write_var(&mut body, v.len() as isize);
+                v.iter().for_each(|&n| write_var(&mut body, n));
+            }
+        }
+
+        body
+    }
+
+    pub fn checksum(&self) -> u32 {
+        checksum(&self.body())
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let body = self.body();
+        let flags = if self.state.is_some() { FLAG_STATE } else { 0 };
+
+        let mut out = Vec::with_capacity(HEADER_LEN + body.len());
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.push(flags);
+        out.extend_from_slice(&(self.memory.len() as u32).to_le_bytes());
+        out.extend_from_slice(&checksum(&body).to_le_bytes());
+        out.extend_from_slice(&body);
+        out
+    }
+
+    pub fn decode(bytes: &[u8]) -> AocResult<Image> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(custom_err("Not an Intcode image"));
+        }
+
+        let version = bytes[4];
+        if version != VERSION {
+            return Err(custom_err(format!("Unsupported image version {}", version)));
+        }
+
+        let flags = bytes[5];
+        let mut word = [0; 4];
+        word.copy_from_slice(&bytes[6..10]);
+        let len = u32::from_le_bytes(word) as usize;
+        word.copy_from_slice(&bytes[10..14]);
+        let sum = u32::from_le_bytes(word);
+
+        let body = &bytes[HEADER_LEN..];
+        if checksum(body) != sum {
+            return Err(custom_err("Image checksum mismatch"));
+        }
+
+        let mut pos = 0;
+        let memory = (0..len)
+            .map(|_| read_var(body, &mut pos))
+            .collect::<AocResult<Vec<_>>>()?;
+
+        let state = if flags & FLAG_STATE != 0 {
+            Some(State {
+                pc: read_var(body, &mut pos)? as usize,
+                base: read_var(body, &mut pos)?,
+                halted: read_var(body, &mut pos)? != 0,
+                input: read_vec(body, &mut pos)?,
+                output: read_vec(body, &mut pos)?,
+            })
+        } else {
+            None
+        };
+
+        if pos != body.len() {
+            return Err(custom_err("Trailing bytes after image"));
+        }
+
+        if let Some(state) = &state {
+            state.check(memory.len())?;
+        }
+
+        Ok(Image { memory, state })
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut s = self
+            .memory
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        s.push('\n');
+        s
+    }
+
+    pub fn detect(bytes: &[u8]) -> Format {
+        if bytes.starts_with(MAGIC) {
+            Format::Binary
+        } else {
+            Format::Text
+        }
+    }
+
+    pub fn parse(bytes: &[u8]) -> AocResult<Image> {
+        match Self::detect(bytes) {
+            Format::Binary => Self::decode(bytes),
+            Format::Text => {
+                let s = std::str::from_utf8(bytes).map_err(|e| AocErr::Other(Box::new(e)))?;
+                Ok(Image {
+                    memory: parse_text(s)?,
+                    state: None,
+                })
+            }
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(p: P) -> AocResult<Image> {
+        Self::parse(&fs::read(p)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, p: P, format: Format) -> AocResult<()> {
+        match format {
+            Format::Binary => fs::write(p, self.encode())?,
+            Format::Text if self.state.is_some() => {
+                return Err(custom_err("Text format cannot hold machine state"))
+            }
+            Format::Text => fs::write(p, self.to_text())?,
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() -> AocResult<()> {
+        assert_eq!(vec![1, -2, 3], parse_text("1, -2,3\n")?);
+        assert_eq!(vec![1, 2, 99], parse_text("# header\n1,2, # args\n 99,\n")?);
+        assert!(parse_text("1,,2").is_err());
+        assert!(parse_text("1,x").is_err());
+
+        let data: Data = parse_file(FileType::Input, 2, 1)?;
+        assert_eq!(
+            data.0.len(),
+            parse_text(&Image::from_data(data.clone()).to_text())?.len()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary() -> AocResult<()> {
+        let data: Data = parse_file(FileType::Input, 9, 1)?;
+        let img = Image::from_data(data);
+        let bytes = img.encode();
+        assert_eq!(Format::Binary, Image::detect(&bytes));
+        assert_eq!(img, Image::decode(&bytes)?);
+
+        let mut corrupt = bytes.clone();
+        *corrupt.last_mut().unwrap() ^= 1;
+        assert!(Image::decode(&corrupt).is_err());
+        assert!(Image::decode(&bytes[..bytes.len() - 1]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_state() -> AocResult<()> {
+        let data: Data = "3,0,4,0,3,0,4,0,99".parse()?;
+        let mut ctx = Context::from_data(data, &[7]);
+        ctx.resume()?;
+        assert!(ctx.resume().is_err());
+
+        let img = Image::decode(&Image::from_context(&ctx).encode())?;
+        assert_eq!(Some(vec![7]), img.state.as_ref().map(|s| s.output.clone()));
+
+        let mut ctx = img.into_context()?;
+        ctx.push_input(11);
+        assert_eq!(11, ctx.exec()?);
+        assert_eq!(&[7, 11], ctx.outputs());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_state_bounds() -> AocResult<()> {
+        let data: Data = "3,0,4,0,99".parse()?;
+        let mut ctx = Context::from_data(data, &[7]);
+        ctx.exec()?;
+        let img = Image::from_context(&ctx);
+        assert_eq!(Some(5), img.state.as_ref().map(|s| s.pc));
+        assert!(Image::decode(&img.encode())?.into_context().is_ok());
+
+        let with = |f: fn(&mut State)| {
+            let mut img = img.clone();
+            if let Some(state) = img.state.as_mut() {
+                f(state);
+            }
+            img
+        };
+        let running = with(|s| s.halted = false);
+        assert!(Image::decode(&running.encode()).is_err());
+        assert!(running.into_context().is_err());
+        assert!(Image::decode(&with(|s| s.pc = 100).encode()).is_err());
+        assert!(Image::decode(&with(|s| s.base = -1).encode()).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_state_base() -> AocResult<()> {
+        //The base is moved far past the end, inputs and outputs use absolute addresses
+        let data: Data = "109,20000,3,0,4,0,99".parse()?;
+        let mut ctx = Context::from_data(data, &[]);
+        assert!(ctx.resume().is_err());
+        assert!(ctx.needs_input());
+
+        let img = Image::decode(&Image::from_context(&ctx).encode())?;
+        assert_eq!(Some(20_000), img.state.as_ref().map(|s| s.base));
+
+        let mut ctx = img.into_context()?;
+        ctx.push_input(5);
+        assert_eq!(5, ctx.exec()?);
+
+        Ok(())
+    }
+}
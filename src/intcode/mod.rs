@@ -0,0 +1,4 @@
+pub mod dis;
+pub mod format;
+pub mod screen;
+pub mod sym;
@@ -0,0 +1,196 @@
+use crate::days::day05::Context;
+use crate::helper::grid::Grid;
+use crate::helper::vec::Vec2;
+use crate::*;
+
+//Output position that reports a score instead of drawing
+pub const SCORE_POS: Vec2 = Vec2::new(-1, 0);
+
+//Largest width and height a screen grows to, a stray draw fails instead of
+//allocating a huge grid
+pub const MAX_SIZE: usize = 4096;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event<T> {
+    Draw(Vec2, T),
+    Score(isize),
+    //The frame is complete, the program waits for input
+    Input,
+    Halt,
+}
+
+//Decodes programs that draw with `x, y, value` output triples. The screen grows
+//to fit every draw, cells never drawn are `empty`
+pub struct Screen<T> {
+    ctx: Context,
+    grid: Grid<T>,
+    empty: T,
+    cell: fn(isize) -> AocResult<T>,
+    score: Option<isize>,
+}
+
+impl<T: Clone> Screen<T> {
+    pub fn new(ctx: Context, empty: T, cell: fn(isize) -> AocResult<T>) -> Screen<T> {
+        Screen {
+            ctx,
+            grid: Grid::new(0, 0, empty.clone()),
+            empty,
+            cell,
+            score: None,
+        }
+    }
+
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn score(&self) -> Option<isize> {
+        self.score
+    }
+
+    pub fn halted(&self) -> bool {
+        self.ctx.halted()
+    }
+
+    pub fn push_input(&mut self, input: isize) {
+        self.ctx.push_input(input);
+    }
+
+    fn grow(&mut self, width: usize, height: usize) {
+        let (w, h) = (self.grid.width(), self.grid.height());
+        if width <= w && height <= h {
+            return;
+        }
+
+        let (old, empty) = (&self.grid, &self.empty);
+        self.grid = Grid::from_fn(width.max(w), height.max(h), |p| {
+            old.get(p).unwrap_or(empty).clone()
+        });
+    }
+
+    fn apply(&mut self, x: isize, y: isize, value: isize) -> AocResult<Event<T>> {
+        let pos = Vec2::new(x, y);
+        if pos == SCORE_POS {
+            self.score = Some(value);
+            return Ok(Event::Score(value));
+        }
+
+        let (ix, iy) = pos
+            .to_index()
+            .ok_or_else(|| custom_err(format!("Draw at negative position {}", pos)))?;
+        if ix >= MAX_SIZE || iy >= MAX_SIZE {
+            return Err(custom_err(format!(
+                "Draw at {} outside the {}x{} screen limit",
+                pos, MAX_SIZE, MAX_SIZE
+            )));
+        }
+
+        let cell = (self.cell)(value)?;
+        self.grow(ix + 1, iy + 1);
+        self.grid[pos] = cell.clone();
+        Ok(Event::Draw(pos, cell))
+    }
+
+    pub fn next_event(&mut self) -> AocResult<Event<T>> {
+        loop {
+            //Decoded outputs are dropped so a long game doesn't keep every draw
+            let pending = self.ctx.outputs().len();
+            if pending >= 3 {
+                let out = self.ctx.take_outputs(3);
+                return self.apply(out[0], out[1], out[2]);
+            }
+
+            if self.ctx.halted() {
+                return match pending {
+                    0 => Ok(Event::Halt),
+                    n => Err(custom_err(format!("Halted after {} of 3 draw values", n))),
+                };
+            }
+
+            if let Err(err) = self.ctx.resume() {
+                return match pending {
+                    _ if !self.ctx.needs_input() => Err(err),
+                    0 => Ok(Event::Input),
+                    n => Err(custom_err(format!(
+                        "Input requested after {} of 3 draw values",
+                        n
+                    ))),
+                };
+            }
+        }
+    }
+
+    //Runs until the program waits for input or halts, returns that last event
+    pub fn frame(&mut self, mut on_event: impl FnMut(&Event<T>)) -> AocResult<Event<T>> {
+        loop {
+            let event = self.next_event()?;
+            on_event(&event);
+            if let Event::Input | Event::Halt = event {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day05::Data;
+
+    fn screen(program: &[isize]) -> Screen<isize> {
+        let ctx = Context::from_data_fill_up(Data(program.to_vec()), &[]);
+        Screen::new(ctx, 0, |v| {
+            if v < 0 {
+                Err(custom_err(format!("Invalid cell {}", v)))
+            } else {
+                Ok(v)
+            }
+        })
+    }
+
+    #[test]
+    fn test_events() -> AocResult<()> {
+        let mut s = screen(&[
+            104, 1, 104, 2, 104, 3, 104, -1, 104, 0, 104, 7, 3, 100, 104, 0, 104, 0, 104, 4, 99,
+        ]);
+        assert_eq!(Event::Draw(Vec2::new(1, 2), 3), s.next_event()?);
+        assert!(s.ctx().outputs().is_empty());
+        assert_eq!((2, 3), (s.grid().width(), s.grid().height()));
+
+        let mut events = Vec::new();
+        assert_eq!(Event::Input, s.frame(|e| events.push(e.clone()))?);
+        assert_eq!(vec![Event::Score(7), Event::Input], events);
+        assert_eq!(Event::Input, s.next_event()?);
+
+        s.push_input(0);
+        assert_eq!(Event::Halt, s.frame(|_| {})?);
+        assert_eq!(Some(7), s.score());
+        assert!(s.ctx().outputs().is_empty());
+        assert_eq!(
+            "4.\n..\n.3",
+            s.grid().render(|&v| if v == 0 {
+                '.'
+            } else {
+                (b'0' + v as u8) as char
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |program: &[isize]| screen(program).frame(|_| {}).err().unwrap().to_string();
+        assert!(err(&[104, 1, 99]).contains("Halted after 1 of 3"));
+        assert!(err(&[104, 1, 104, 2, 3, 100, 99]).contains("Input requested after 2 of 3"));
+        assert!(err(&[104, -2, 104, 0, 104, 1, 99]).contains("negative position"));
+        assert!(err(&[104, 0, 104, 1_000_000_000, 104, 1, 99]).contains("screen limit"));
+        assert!(err(&[104, 4096, 104, 0, 104, 1, 99]).contains("screen limit"));
+        assert!(err(&[104, 0, 104, 0, 104, -1, 99]).contains("Invalid cell"));
+        assert!(err(&[77]).contains("Invalid Opcode"));
+    }
+}
@@ -0,0 +1,669 @@
+use crate::days::day05::Data;
+use crate::*;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::ops::{Add, Mul};
+
+const MAX_STEPS: usize = 500_000;
+const MAX_ENUM: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Symbol {
+    Mem(usize),
+    Input(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Const(isize),
+    Sym(Symbol),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Lt(Box<Expr>, Box<Expr>),
+    Eq(Box<Expr>, Box<Expr>),
+    //Value loaded from a symbolic address or an overflowing constant
+    Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Linear {
+    pub konst: isize,
+    pub coeffs: BTreeMap<Symbol, isize>,
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Symbol::Mem(ix) => write!(f, "mem[{}]", ix),
+            Symbol::Input(ix) => write!(f, "in[{}]", ix),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Const(n) => write!(f, "{}", n),
+            Expr::Sym(sym) => write!(f, "{}", sym),
+            Expr::Add(a, b) => write!(f, "({} + {})", a, b),
+            Expr::Mul(a, b) => write!(f, "({} * {})", a, b),
+            Expr::Lt(a, b) => write!(f, "({} < {})", a, b),
+            Expr::Eq(a, b) => write!(f, "({} == {})", a, b),
+            Expr::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+impl Add for Expr {
+    type Output = Expr;
+
+    fn add(self, other: Expr) -> Expr {
+        match (self, other) {
+            (Expr::Const(a), Expr::Const(b)) => a.checked_add(b).map_or(Expr::Unknown, Expr::Const),
+            (Expr::Const(0), e) | (e, Expr::Const(0)) => e,
+            (a, b) => Expr::Add(Box::new(a), Box::new(b)),
+        }
+    }
+}
+
+impl Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, other: Expr) -> Expr {
+        match (self, other) {
+            (Expr::Const(a), Expr::Const(b)) => a.checked_mul(b).map_or(Expr::Unknown, Expr::Const),
+            (Expr::Const(0), _) | (_, Expr::Const(0)) => Expr::Const(0),
+            (Expr::Const(1), e) | (e, Expr::Const(1)) => e,
+            (a, b) => Expr::Mul(Box::new(a), Box::new(b)),
+        }
+    }
+}
+
+impl Expr {
+    pub fn cmp_lt(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const((a < b) as isize),
+            (a, b) => Expr::Lt(Box::new(a), Box::new(b)),
+        }
+    }
+
+    pub fn cmp_eq(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const((a == b) as isize),
+            (a, b) => Expr::Eq(Box::new(a), Box::new(b)),
+        }
+    }
+
+    pub fn as_const(&self) -> Option<isize> {
+        match self {
+            Expr::Const(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn has_unknown(&self) -> bool {
+        match self {
+            Expr::Unknown => true,
+            Expr::Const(_) | Expr::Sym(_) => false,
+            Expr::Add(a, b) | Expr::Mul(a, b) | Expr::Lt(a, b) | Expr::Eq(a, b) => {
+                a.has_unknown() || b.has_unknown()
+            }
+        }
+    }
+
+    pub fn eval(&self, env: &HashMap<Symbol, isize>) -> Option<isize> {
+        Some(match self {
+            Expr::Const(n) => *n,
+            Expr::Sym(sym) => *env.get(sym)?,
+            Expr::Add(a, b) => a.eval(env)?.checked_add(b.eval(env)?)?,
+            Expr::Mul(a, b) => a.eval(env)?.checked_mul(b.eval(env)?)?,
+            Expr::Lt(a, b) => (a.eval(env)? < b.eval(env)?) as isize,
+            Expr::Eq(a, b) => (a.eval(env)? == b.eval(env)?) as isize,
+            Expr::Unknown => return None,
+        })
+    }
+
+    //None for nonlinear expressions and ones whose coefficients overflow
+    pub fn linear(&self) -> Option<Linear> {
+        match self {
+            Expr::Const(n) => Some(Linear {
+                konst: *n,
+                coeffs: BTreeMap::new(),
+            }),
+            Expr::Sym(sym) => Some(Linear {
+                konst: 0,
+                coeffs: [(*sym, 1)].iter().cloned().collect(),
+            }),
+            Expr::Add(a, b) => {
+                let mut a = a.linear()?;
+                let b = b.linear()?;
+                a.konst = a.konst.checked_add(b.konst)?;
+                for (sym, c) in b.coeffs {
+                    let coeff = a.coeffs.entry(sym).or_insert(0);
+                    *coeff = coeff.checked_add(c)?;
+                }
+                a.coeffs.retain(|_, c| *c != 0);
+                Some(a)
+            }
+            Expr::Mul(a, b) => {
+                let (a, b) = (a.linear()?, b.linear()?);
+                let (scale, mut lin) = match (a.coeffs.is_empty(), b.coeffs.is_empty()) {
+                    (true, _) => (a.konst, b),
+                    (_, true) => (b.konst, a),
+                    _ => return None,
+                };
+                lin.konst = lin.konst.checked_mul(scale)?;
+                for c in lin.coeffs.values_mut() {
+                    *c = c.checked_mul(scale)?;
+                }
+                lin.coeffs.retain(|_, c| *c != 0);
+                Some(lin)
+            }
+            Expr::Lt(..) | Expr::Eq(..) | Expr::Unknown => None,
+        }
+    }
+}
+
+pub struct SymContext {
+    data: Vec<Expr>,
+    input: VecDeque<Expr>,
+    output: Vec<Expr>,
+    pc: usize,
+    base: isize,
+    halted: bool,
+}
+
+impl SymContext {
+    pub fn from_data(data: Data, inputs: &[Expr]) -> SymContext {
+        SymContext {
+            data: data.0.into_iter().map(Expr::Const).collect(),
+            input: inputs.iter().cloned().collect(),
+            output: Vec::new(),
+            pc: 0,
+            base: 0,
+            halted: false,
+        }
+    }
+
+    pub fn symbolize(&mut self, ix: usize) -> AocResult<()> {
+        let cell = self
+            .data
+            .get_mut(ix)
+            .ok_or_else(|| custom_err(format!("Symbol out of bounds: mem[{}]", ix)))?;
+        *cell = Expr::Sym(Symbol::Mem(ix));
+        Ok(())
+    }
+
+    pub fn read(&self, ix: usize) -> Option<&Expr> {
+        self.data.get(ix)
+    }
+
+    pub fn outputs(&self) -> &[Expr] {
+        &self.output
+    }
+
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
+    fn concrete(&self, ix: usize, what: &str) -> AocResult<isize> {
+        self.data
+            .get(ix)
+            .ok_or_else(|| custom_err(format!("Out of bounds {} at {}", what, ix)))?
+            .as_const()
+            .ok_or_else(|| custom_err(format!("Symbolic {} at {}", what, ix)))
+    }
+
+    fn address(&self, param: usize, mode: isize) -> AocResult<Option<usize>> {
+        let param = self
+            .data
+            .get(self.pc + param)
+            .cloned()
+            .unwrap_or(Expr::Const(0));
+        let ix = match (mode, param) {
+            (0, Expr::Const(ix)) => ix,
+            (2, Expr::Const(off)) => self
+                .base
+                .checked_add(off)
+                .ok_or_else(|| custom_err("Relative address overflow"))?,
+            (0, _) | (2, _) => return Ok(None),
+            (mode, _) => return Err(custom_err(format!("Invalid mode {}", mode))),
+        };
+
+        if ix < 0 || ix as usize >= self.data.len() {
+            return Err(custom_err(format!("Address out of bounds: {}", ix)));
+        }
+
+        Ok(Some(ix as usize))
+    }
+
+    fn read_param(&self, param: usize, mode: isize) -> AocResult<Expr> {
+        if mode == 1 {
+            return Ok(self
+                .data
+                .get(self.pc + param)
+                .cloned()
+                .unwrap_or(Expr::Const(0)));
+        }
+
+        Ok(match self.address(param, mode)? {
+            Some(ix) => self.data[ix].clone(),
+            None => Expr::Unknown,
+        })
+    }
+
+    fn write_param(&mut self, param: usize, mode: isize, value: Expr) -> AocResult<()> {
+        match self.address(param, mode)? {
+            Some(ix) => {
+                self.data[ix] = value;
+                Ok(())
+            }
+            None => Err(custom_err("Write to symbolic address")),
+        }
+    }
+
+    fn jump_target(&self, param: usize, mode: isize) -> AocResult<usize> {
+        self.read_param(param, mode)?
+            .as_const()
+            .map(|dst| dst as usize)
+            .ok_or_else(|| custom_err("Symbolic jump target"))
+    }
+
+    pub fn run(&mut self) -> AocResult<()> {
+        for _ in 0..MAX_STEPS {
+            if self.halted {
+                return Ok(());
+            }
+
+            let op = self.concrete(self.pc, "opcode")?;
+            let modes = [(op / 100) % 10, (op / 1_000) % 10, (op / 10_000) % 10];
+
+            match op % 100 {
+                1 | 2 | 7 | 8 => {
+                    let a = self.read_param(1, modes[0])?;
+                    let b = self.read_param(2, modes[1])?;
+                    let value = match op % 100 {
+                        1 => a + b,
+                        2 => a * b,
+                        7 => Expr::cmp_lt(a, b),
+                        _ => Expr::cmp_eq(a, b),
+                    };
+                    self.write_param(3, modes[2], value)?;
+                    self.pc += 4;
+                }
+                3 => {
+                    let value = self
+                        .input
+                        .pop_front()
+                        .ok_or_else(|| custom_err("Not enough inputs"))?;
+                    self.write_param(1, modes[0], value)?;
+                    self.pc += 2;
+                }
+                4 => {
+                    let value = self.read_param(1, modes[0])?;
+                    self.output.push(value);
+                    self.pc += 2;
+                }
+                5 | 6 => {
+                    let cond = self
+                        .read_param(1, modes[0])?
+                        .as_const()
+                        .ok_or_else(|| custom_err(format!("Symbolic branch at {}", self.pc)))?;
+
+                    if (cond != 0) == (op % 100 == 5) {
+                        self.pc = self.jump_target(2, modes[1])?;
+                    } else {
+                        self.pc += 3;
+                    }
+                }
+                9 => {
+                    let off = self
+                        .read_param(1, modes[0])?
+                        .as_const()
+                        .ok_or_else(|| custom_err("Symbolic relative base"))?;
+                    self.base = self
+                        .base
+                        .checked_add(off)
+                        .ok_or_else(|| custom_err("Relative base overflow"))?;
+                    self.pc += 2;
+                }
+                99 => self.halted = true,
+                _ => return Err(custom_err(format!("Invalid Opcode {}", op))),
+            }
+        }
+
+        Err(custom_err("Exceeded max steps"))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Target {
+    Mem(usize),
+    Output(usize),
+}
+
+pub struct Solver {
+    data: Data,
+    inputs: Vec<Expr>,
+    vars: Vec<(Symbol, RangeInclusive<isize>)>,
+    max_enum: usize,
+}
+
+impl Solver {
+    pub fn new(data: Data) -> Solver {
+        Solver {
+            data,
+            inputs: Vec::new(),
+            vars: Vec::new(),
+            max_enum: MAX_ENUM,
+        }
+    }
+
+    pub fn var_mem(mut self, ix: usize, domain: RangeInclusive<isize>) -> Solver {
+        self.vars.push((Symbol::Mem(ix), domain));
+        self
+    }
+
+    pub fn var_input(mut self, domain: RangeInclusive<isize>) -> Solver {
+        let sym = Symbol::Input(self.inputs.len());
+        self.inputs.push(Expr::Sym(sym));
+        self.vars.push((sym, domain));
+        self
+    }
+
+    pub fn input(mut self, value: isize) -> Solver {
+        self.inputs.push(Expr::Const(value));
+        self
+    }
+
+    pub fn max_enum(mut self, max_enum: usize) -> Solver {
+        self.max_enum = max_enum;
+        self
+    }
+
+    //Memory vars and targets must lie inside the program
+    fn check(&self, target: Target) -> AocResult<()> {
+        let mem = self.vars.iter().filter_map(|(sym, _)| match sym {
+            Symbol::Mem(ix) => Some(*ix),
+            Symbol::Input(_) => None,
+        });
+        let target = match target {
+            Target::Mem(ix) => Some(ix),
+            Target::Output(_) => None,
+        };
+
+        match mem.chain(target).find(|&ix| ix >= self.data.0.len()) {
+            Some(ix) => Err(custom_err(format!("Out of bounds mem[{}]", ix))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn expr(&self, target: Target) -> AocResult<Expr> {
+        self.check(target)?;
+
+        let mut ctx = SymContext::from_data(self.data.clone(), &self.inputs);
+        for (sym, _) in self.vars.iter() {
+            if let Symbol::Mem(ix) = sym {
+                ctx.symbolize(*ix)?;
+            }
+        }
+
+        ctx.run()?;
+
+        Ok(match target {
+            Target::Mem(ix) => ctx
+                .read(ix)
+                .cloned()
+                .ok_or_else(|| custom_err(format!("No mem[{}]", ix)))?,
+            Target::Output(ix) => ctx
+                .outputs()
+                .get(ix)
+                .cloned()
+                .ok_or_else(|| custom_err(format!("No output {}", ix)))?,
+        })
+    }
+
+    //Returns the lexicographically smallest assignment of the vars
+    pub fn solve(&self, target: Target, value: isize) -> AocResult<Option<Vec<isize>>> {
+        self.check(target)?;
+
+        match self.expr(target) {
+            Ok(expr) => match expr.linear() {
+                Some(lin) => self.solve_linear(&lin, value),
+                None if !expr.has_unknown() => self.enumerate(self.vars.len(), |env| {
+                    Ok(expr.eval(&self.env(env)) == Some(value))
+                }),
+                None => self.enumerate_concrete(target, value),
+            },
+            Err(_) => self.enumerate_concrete(target, value),
+        }
+    }
+
+    fn env(&self, values: &[isize]) -> HashMap<Symbol, isize> {
+        self.vars
+            .iter()
+            .map(|(sym, _)| *sym)
+            .zip(values.iter().cloned())
+            .collect()
+    }
+
+    fn solve_linear(&self, lin: &Linear, value: isize) -> AocResult<Option<Vec<isize>>> {
+        let coeff = |ix: usize| lin.coeffs.get(&self.vars[ix].0).cloned().unwrap_or(0);
+        //The machine can't produce a value that needs an overflow on the way
+        let rest = match value.checked_sub(lin.konst) {
+            Some(rest) => rest,
+            None => return Ok(None),
+        };
+
+        let solved = match (0..self.vars.len()).rev().find(|&ix| coeff(ix) != 0) {
+            Some(solved) => solved,
+            None if rest == 0 => {
+                return Ok(Some(self.vars.iter().map(|(_, d)| *d.start()).collect()))
+            }
+            None => return Ok(None),
+        };
+
+        let mut result = None;
+        self.enumerate(solved, |prefix| {
+            let rest = prefix.iter().enumerate().try_fold(rest, |rest, (ix, v)| {
+                rest.checked_sub(coeff(ix).checked_mul(*v)?)
+            });
+            let rest = match rest {
+                Some(rest) => rest,
+                None => return Ok(false),
+            };
+
+            let c = coeff(solved);
+            if rest % c != 0 || !self.vars[solved].1.contains(&(rest / c)) {
+                return Ok(false);
+            }
+
+            let mut values = prefix.to_vec();
+            values.push(rest / c);
+            values.extend(self.vars[solved + 1..].iter().map(|(_, d)| *d.start()));
+            result = Some(values);
+            Ok(true)
+        })?;
+
+        Ok(result)
+    }
+
+    //Runs every candidate on a fully concrete `SymContext`, which bounds checks
+    //each access, so values that become invalid addresses are skipped
+    fn enumerate_concrete(&self, target: Target, value: isize) -> AocResult<Option<Vec<isize>>> {
+        let expected = Expr::Const(value);
+        self.enumerate(self.vars.len(), |values| {
+            let mut data = self.data.clone();
+            let mut inputs = Vec::new();
+            for (expr, var_ix) in self.inputs.iter().map(|e| (e, self.var_ix(e))) {
+                match (expr, var_ix) {
+                    (_, Some(ix)) => inputs.push(Expr::Const(values[ix])),
+                    (Expr::Const(n), None) => inputs.push(Expr::Const(*n)),
+                    _ => unreachable!(),
+                }
+            }
+
+            for ((sym, _), v) in self.vars.iter().zip(values) {
+                if let Symbol::Mem(ix) = sym {
+                    data.0[*ix] = *v;
+                }
+            }
+
+            let mut ctx = SymContext::from_data(data, &inputs);
+            if ctx.run().is_err() {
+                return Ok(false);
+            }
+
+            Ok(match target {
+                Target::Mem(ix) => ctx.read(ix) == Some(&expected),
+                Target::Output(ix) => ctx.outputs().get(ix) == Some(&expected),
+            })
+        })
+    }
+
+    fn var_ix(&self, expr: &Expr) -> Option<usize> {
+        self.vars
+            .iter()
+            .position(|(sym, _)| expr == &Expr::Sym(*sym))
+    }
+
+    //Runs `f` on every assignment of the first `n` vars until it returns true
+    fn enumerate<F>(&self, n: usize, mut f: F) -> AocResult<Option<Vec<isize>>>
+    where
+        F: FnMut(&[isize]) -> AocResult<bool>,
+    {
+        let domains = &self.vars[..n];
+        let total = domains.iter().try_fold(1_usize, |total, (_, d)| {
+            let len = (*d.end() as i128 - *d.start() as i128 + 1).max(0);
+            total.checked_mul(usize::try_from(len).ok()?)
+        });
+
+        match total {
+            Some(0) => return Ok(None),
+            Some(total) if total <= self.max_enum => {}
+            _ => return Err(custom_err("Search space exceeds enumeration bound")),
+        }
+
+        let mut values: Vec<isize> = domains.iter().map(|(_, d)| *d.start()).collect();
+        loop {
+            if f(&values)? {
+                return Ok(Some(values));
+            }
+
+            //Increment like an odometer, last var fastest
+            let mut ix = n;
+            loop {
+                if ix == 0 {
+                    return Ok(None);
+                }
+                ix -= 1;
+
+                if values[ix] < *domains[ix].1.end() {
+                    values[ix] += 1;
+                    break;
+                }
+                values[ix] = *domains[ix].1.start();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear() -> AocResult<()> {
+        let data: Data = parse_file(FileType::Input, 2, 1)?;
+        let expr = Solver::new(data)
+            .var_mem(1, 0..=99)
+            .var_mem(2, 0..=99)
+            .expr(Target::Mem(0))?;
+
+        let lin = expr.linear().unwrap();
+        assert_eq!(lin.coeffs[&Symbol::Mem(2)], 1);
+        assert_eq!(lin.coeffs.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_input() -> AocResult<()> {
+        let data: Data = "3,0,1001,0,7,0,4,0,99".parse()?;
+        let solver = Solver::new(data).var_input(-100..=100);
+
+        assert_eq!(Some(vec![3]), solver.solve(Target::Output(0), 10)?);
+        assert_eq!(Some(vec![-100]), solver.solve(Target::Output(0), -93)?);
+        assert_eq!(None, solver.solve(Target::Output(0), 200)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nonlinear() -> AocResult<()> {
+        let data: Data = "2,5,5,0,99,0".parse()?;
+        let solver = Solver::new(data).var_mem(5, 0..=20);
+
+        assert!(solver.expr(Target::Mem(0))?.linear().is_none());
+        assert_eq!(Some(vec![12]), solver.solve(Target::Mem(0), 144)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_branch() -> AocResult<()> {
+        let data: Data = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99".parse()?;
+        let solver = Solver::new(data).var_input(0..=20);
+
+        assert!(solver.expr(Target::Output(0)).is_err());
+        assert_eq!(Some(vec![8]), solver.solve(Target::Output(0), 1000)?);
+        assert_eq!(Some(vec![9]), solver.solve(Target::Output(0), 1001)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() -> AocResult<()> {
+        assert_eq!(Expr::Unknown, Expr::Const(isize::MAX) + Expr::Const(1));
+        assert_eq!(Expr::Unknown, Expr::Const(isize::MAX) * Expr::Const(2));
+
+        //mem[0] = mem[9] * (MAX / 2) + mem[10]
+        let data = Data(vec![1002, 9, isize::MAX / 2, 0, 1, 0, 10, 0, 99, 0, 0]);
+        let solver = Solver::new(data).var_mem(9, 0..=5).var_mem(10, 0..=5);
+        assert_eq!(None, solver.solve(Target::Mem(0), 7)?);
+        assert_eq!(Some(vec![0, 5]), solver.solve(Target::Mem(0), 5)?);
+
+        let data: Data = "1,0,0,0,99".parse()?;
+        let solver = Solver::new(data).var_mem(1, isize::MIN..=isize::MAX);
+        assert!(solver.solve(Target::Mem(0), 2).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bounds() -> AocResult<()> {
+        //mem[1] is the address of the first operand
+        let data: Data = "1,0,0,0,99".parse()?;
+        let solver = Solver::new(data.clone()).var_mem(1, 0..=1000);
+
+        assert_eq!(Some(vec![4]), solver.solve(Target::Mem(0), 100)?);
+        assert_eq!(None, solver.solve(Target::Mem(0), 1000)?);
+
+        assert!(Solver::new(data.clone())
+            .var_mem(5, 0..=9)
+            .solve(Target::Mem(0), 1)
+            .is_err());
+        assert!(Solver::new(data.clone())
+            .var_mem(1, 0..=9)
+            .expr(Target::Mem(5))
+            .is_err());
+
+        let mut ctx = SymContext::from_data(data, &[]);
+        assert!(ctx.symbolize(5).is_err());
+        assert_eq!(None, ctx.read(5));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use aoc19::intcode::dis::{disassemble, opcode_name};
+use aoc19::intcode::format::{Format, Image};
+use aoc19::{custom_err, AocResult};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const USAGE: &str = "usage:
+  intcode run <file> [input...] [--save <file>]
+  intcode dis <file>
+  intcode stat <file>
+  intcode convert <from> <to>";
+
+const MEM_SIZE: usize = 16_000;
+
+fn run(args: &[String]) -> AocResult<()> {
+    let (file, rest) = args.split_first().ok_or_else(|| custom_err(USAGE))?;
+    let mut img = Image::load(file)?;
+    let mut save = None;
+    let mut inputs = Vec::new();
+
+    let mut it = rest.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--save" => save = Some(it.next().ok_or_else(|| custom_err(USAGE))?),
+            n => inputs.push(n.parse()?),
+        }
+    }
+
+    if img.state.is_none() && img.memory.len() < MEM_SIZE {
+        img.memory.resize(MEM_SIZE, 0);
+    }
+
+    let mut ctx = img.into_context()?;
+    let out_start = ctx.outputs().len();
+    inputs.into_iter().for_each(|n| ctx.push_input(n));
+
+    let mut blocked = false;
+    while !ctx.halted() {
+        if let Err(err) = ctx.resume() {
+            //Only a machine waiting for input can be saved and resumed later
+            if !ctx.needs_input() || save.is_none() {
+                return Err(err);
+            }
+            blocked = true;
+            break;
+        }
+    }
+
+    println!("{}", ctx.outputs()[out_start..].iter().join(","));
+
+    if let Some(save) = save {
+        if blocked {
+            eprintln!("waiting for input, state saved to {}", save);
+        }
+        Image::from_context(&ctx).save(save, Format::Binary)?;
+    }
+
+    Ok(())
+}
+
+fn dis(args: &[String]) -> AocResult<()> {
+    let file = args.first().ok_or_else(|| custom_err(USAGE))?;
+    let img = Image::load(file)?;
+
+    for line in disassemble(&img.memory) {
+        let cells = img.memory[line.addr..line.addr + line.len].iter().join(",");
+        println!("{:05}  {:<24} {}", line.addr, cells, line.text);
+    }
+
+    Ok(())
+}
+
+fn stat(args: &[String]) -> AocResult<()> {
+    let file = args.first().ok_or_else(|| custom_err(USAGE))?;
+    let bytes = std::fs::read(file)?;
+    let img = Image::parse(&bytes)?;
+
+    let lines = disassemble(&img.memory);
+    let mut histogram = BTreeMap::new();
+    for line in lines.iter() {
+        let name = opcode_name(img.memory[line.addr])
+            .filter(|_| !line.text.starts_with(".data"))
+            .unwrap_or(".data");
+        *histogram.entry(name).or_insert(0) += 1;
+    }
+
+    println!("format:    {:?}", Image::detect(&bytes));
+    println!("size:      {} bytes", bytes.len());
+    println!("cells:     {}", img.memory.len());
+    println!("checksum:  {:08x}", img.checksum());
+    match &img.state {
+        Some(state) => println!(
+            "state:     pc={} base={} halted={} inputs={} outputs={}",
+            state.pc,
+            state.base,
+            state.halted,
+            state.input.len(),
+            state.output.len()
+        ),
+        None => println!("state:     none"),
+    }
+
+    for (name, count) in histogram {
+        println!("  {:<6} {}", name, count);
+    }
+
+    Ok(())
+}
+
+fn convert(args: &[String]) -> AocResult<()> {
+    let (from, to) = match args {
+        [from, to] => (from, to),
+        _ => return Err(custom_err(USAGE)),
+    };
+
+    let img = Image::load(from)?;
+    img.save(to, Format::from_path(Path::new(to)))
+}
+
+fn main() -> AocResult<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (cmd, rest) = args.split_first().ok_or_else(|| custom_err(USAGE))?;
+
+    match cmd.as_str() {
+        "run" => run(rest),
+        "dis" => dis(rest),
+        "stat" => stat(rest),
+        "convert" => convert(rest),
+        _ => Err(custom_err(USAGE)),
+    }
+}
@@ -1,5 +1,11 @@
+pub mod bench;
+pub mod breakout;
 pub mod days;
 pub mod helper;
+pub mod import;
+pub mod intcode;
+pub mod parse;
+pub mod solution;
 
 use std::fs;
 use std::io::Read;
@@ -7,18 +13,18 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use thiserror::Error;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum FileType {
     Input,
     Example,
 }
 
-impl ToString for FileType {
-    fn to_string(&self) -> String {
-        match self {
+impl std::fmt::Display for FileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
             FileType::Input => "in",
             FileType::Example => "ex",
-        }
-        .to_string()
+        })
     }
 }
 
@@ -26,34 +32,331 @@ pub fn custom_err<S: ToString>(s: S) -> AocErr {
     AocErr::Custom(s.to_string())
 }
 
+pub const DATA_ENV: &str = "AOC19_DATA";
+pub const PROFILE_ENV: &str = "AOC19_PROFILE";
+pub const CONFIG_FILE: &str = "aoc19.conf";
+
+//Reads `key = value` from the config file in the working directory
+fn config_value(key: &str) -> Option<String> {
+    let s = fs::read_to_string(CONFIG_FILE).ok()?;
+    s.lines()
+        .filter(|l| !l.trim_start().starts_with('#'))
+        .filter_map(|l| {
+            let ix = l.find('=')?;
+            Some((l[..ix].trim(), l[ix + 1..].trim()))
+        })
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v.to_string())
+}
+
+fn setting(env: &str, key: &str) -> Option<String> {
+    std::env::var(env)
+        .ok()
+        .filter(|v| !v.is_empty())
+        .or_else(|| config_value(key))
+}
+
+pub fn data_root() -> PathBuf {
+    match setting(DATA_ENV, "data") {
+        Some(root) => PathBuf::from(root),
+        None => {
+            let mut cwd = std::env::current_dir().unwrap();
+            cwd.push("data");
+            cwd
+        }
+    }
+}
+
+fn is_day_dir(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_digit())
+}
+
+//The unnamed profile uses `data/NN`, named ones `data/<name>/NN`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub name: Option<String>,
+    root: PathBuf,
+}
+
+impl Profile {
+    pub fn current() -> Profile {
+        Profile {
+            name: setting(PROFILE_ENV, "profile"),
+            root: data_root(),
+        }
+    }
+
+    pub fn named(name: Option<&str>) -> Profile {
+        Profile {
+            name: name.map(|n| n.to_string()),
+            root: data_root(),
+        }
+    }
+
+    pub fn with_root(root: PathBuf, name: Option<&str>) -> Profile {
+        Profile {
+            name: name.map(|n| n.to_string()),
+            root,
+        }
+    }
+
+    pub fn all() -> AocResult<Vec<Profile>> {
+        let root = data_root();
+        let mut has_default = false;
+        let mut names = Vec::new();
+
+        for entry in root.read_dir()? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+
+            match entry.file_name().to_str() {
+                Some(name) if is_day_dir(name) => has_default = true,
+                Some(name) => names.push(name.to_string()),
+                None => {}
+            }
+        }
+
+        names.sort();
+        let default = if has_default { Some(None) } else { None };
+
+        Ok(default
+            .into_iter()
+            .chain(names.into_iter().map(Some))
+            .map(|name| Profile {
+                name,
+                root: root.clone(),
+            })
+            .collect())
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+
+    pub fn path(&self) -> PathBuf {
+        let mut p = self.root.clone();
+        if let Some(name) = &self.name {
+            p.push(name);
+        }
+        p
+    }
+
+    pub fn day_path(&self, day: usize) -> PathBuf {
+        let mut p = self.path();
+        p.push(format!("{:02}", day));
+        p
+    }
+
+    pub fn file_path(&self, file_type: FileType, day: usize, task: usize) -> PathBuf {
+        let mut p = self.day_path(day);
+        p.push(format!("{}_{:02}.data", file_type, task));
+        p
+    }
+
+    pub fn answers_path(&self, file_type: FileType, day: usize, task: usize) -> PathBuf {
+        self.file_path(file_type, day, task).with_extension("answers")
+    }
+
+    pub fn read_file(&self, file_type: FileType, day: usize, task: usize) -> AocResult<String> {
+        let mut f = fs::File::open(self.file_path(file_type, day, task))?;
+        let mut s = String::new();
+        f.read_to_string(&mut s)?;
+        Ok(s)
+    }
+
+    pub fn parse_file<T: FromStr>(&self, file_type: FileType, day: usize, task: usize) -> AocResult<T>
+    where
+        <T as FromStr>::Err: std::error::Error + 'static,
+    {
+        self.read_file(file_type, day, task)?
+            .parse()
+            .map_err(|err| parse::in_file(err, &self.file_path(file_type, day, task)))
+    }
+
+    pub fn load_answers(&self, file_type: FileType, day: usize, task: usize) -> AocResult<Answers> {
+        match fs::read_to_string(self.answers_path(file_type, day, task)) {
+            Ok(s) => s.parse(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save_answers(
+        &self,
+        file_type: FileType,
+        day: usize,
+        task: usize,
+        answers: &Answers,
+    ) -> AocResult<()> {
+        fs::write(self.answers_path(file_type, day, task), answers.to_string())?;
+        Ok(())
+    }
+
+    //Passes when the expected answer is unknown so other inputs can be run
+    pub fn check_answer<T: std::fmt::Display>(
+        &self,
+        file_type: FileType,
+        day: usize,
+        task: usize,
+        part: usize,
+        answer: T,
+    ) -> AocResult<()> {
+        let answer = answer.to_string();
+        match self.load_answers(file_type, day, task)?.get(part) {
+            Some(expected) if expected != answer => Err(custom_err(format!(
+                "Day {} part {} ({}): expected {}, got {}",
+                day,
+                part,
+                self.display_name(),
+                expected,
+                answer
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn record_answer(
+        &self,
+        file_type: FileType,
+        day: usize,
+        task: usize,
+        part: usize,
+        answer: String,
+    ) -> AocResult<()> {
+        let mut answers = self.load_answers(file_type, day, task)?;
+        answers.set(part, answer)?;
+        self.save_answers(file_type, day, task, &answers)
+    }
+}
+
 pub fn data_path() -> PathBuf {
-    let mut cwd = std::env::current_dir().unwrap();
-    cwd.push("data");
-    cwd
+    Profile::current().path()
 }
 
 pub fn day_path(day: usize) -> PathBuf {
-    let mut p = data_path();
-    p.push(format!("{:02}", day));
-    p
+    Profile::current().day_path(day)
 }
 
 pub fn file_path(file_type: FileType, day: usize, task: usize) -> PathBuf {
-    let mut p = day_path(day);
-    p.push(format!("{}_{:02}.data", file_type.to_string(), task));
-    p
+    Profile::current().file_path(file_type, day, task)
 }
 
 pub fn parse_file<T: FromStr>(file_type: FileType, day: usize, task: usize) -> AocResult<T>
 where
     <T as FromStr>::Err: std::error::Error + 'static,
 {
-    let p = file_path(file_type, day, task);
-    let mut f = fs::File::open(p)?;
-    let mut s = String::new();
-    f.read_to_string(&mut s)?;
+    Profile::current().parse_file(file_type, day, task)
+}
 
-    s.parse().map_err(|err| AocErr::Other(Box::new(err)))
+pub fn answers_path(file_type: FileType, day: usize, task: usize) -> PathBuf {
+    Profile::current().answers_path(file_type, day, task)
+}
+
+//One `partN: value` per line, `?` or a missing line means unknown
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers(pub [Option<String>; 2]);
+
+fn escape_answer(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape_answer(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            (c, _) => out.push(c),
+        }
+    }
+    out
+}
+
+impl Answers {
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.0.get(part.wrapping_sub(1))?.as_deref()
+    }
+
+    pub fn set(&mut self, part: usize, answer: String) -> AocResult<()> {
+        let slot = self
+            .0
+            .get_mut(part.wrapping_sub(1))
+            .ok_or_else(|| custom_err(format!("Invalid part {}", part)))?;
+        *slot = Some(answer);
+        Ok(())
+    }
+}
+
+impl FromStr for Answers {
+    type Err = AocErr;
+
+    fn from_str(s: &str) -> AocResult<Self> {
+        let mut answers = Answers::default();
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let ix = line
+                .find(':')
+                .ok_or_else(|| custom_err(format!("Invalid answer line: {}", line)))?;
+
+            //Only the separating space is stripped, rendered answers keep their padding
+            let value = line[ix + 1..].trim_end_matches('\r');
+            let (key, value) = (line[..ix].trim(), value.strip_prefix(' ').unwrap_or(value));
+
+            let part = match key {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(custom_err(format!("Unknown answer key: {}", key))),
+            };
+
+            if value != "?" {
+                answers.set(part, unescape_answer(value))?;
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in 1..=2 {
+            let value = self.get(part).map(escape_answer);
+            writeln!(f, "part{}: {}", part, value.as_deref().unwrap_or("?"))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn load_answers(file_type: FileType, day: usize, task: usize) -> AocResult<Answers> {
+    Profile::current().load_answers(file_type, day, task)
+}
+
+pub fn check_answer<T: std::fmt::Display>(
+    file_type: FileType,
+    day: usize,
+    task: usize,
+    part: usize,
+    answer: T,
+) -> AocResult<()> {
+    Profile::current().check_answer(file_type, day, task, part, answer)
+}
+
+pub fn record_answer(
+    file_type: FileType,
+    day: usize,
+    task: usize,
+    part: usize,
+    answer: String,
+) -> AocResult<()> {
+    Profile::current().record_answer(file_type, day, task, part, answer)
 }
 
 pub struct ParseLineVec<T>(pub Vec<T>);
@@ -66,8 +369,8 @@ where
 
     fn from_str(s: &str) -> AocResult<Self> {
         let mut v = Vec::new();
-        for line in s.lines() {
-            v.push(T::from_str(line).map_err(|err| AocErr::Other(Box::new(err)))?)
+        for (ix, line) in s.lines().enumerate() {
+            v.push(T::from_str(line).map_err(|err| parse::on_line(err, ix, line))?)
         }
 
         Ok(ParseLineVec(v))
@@ -81,6 +384,8 @@ pub enum AocErr {
     IoError(#[from] std::io::Error),
     #[error("Parse Int parse")]
     ParseIntError(#[from] std::num::ParseIntError),
+    #[error("{0}")]
+    Parse(parse::ParseError),
     #[error("Custom: {0}")]
     Custom(String),
     #[error("Other: {0}")]
@@ -88,3 +393,33 @@ pub enum AocErr {
 }
 
 pub type AocResult<T> = Result<T, AocErr>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers() -> AocResult<()> {
+        let answers: Answers = "part1: 42\npart2: ?\n".parse()?;
+        assert_eq!(Some("42"), answers.get(1));
+        assert_eq!(None, answers.get(2));
+        assert_eq!(None, answers.get(3));
+
+        let mut answers = Answers::default();
+        answers.set(2, "#..\n.\\#".to_string())?;
+        assert_eq!("part1: ?\npart2: #..\\n.\\\\#\n", answers.to_string());
+        assert_eq!(answers, answers.to_string().parse()?);
+
+        assert!("part3: 1".parse::<Answers>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_answer() -> AocResult<()> {
+        check_answer(FileType::Input, 1, 1, 1, 3_297_896)?;
+        assert!(check_answer(FileType::Input, 1, 1, 1, 1).is_err());
+        check_answer(FileType::Example, 1, 1, 1, 1)?;
+
+        Ok(())
+    }
+}
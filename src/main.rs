@@ -0,0 +1,130 @@
+use aoc19::days::registry;
+use aoc19::parse::in_file;
+use aoc19::solution::{parse_days, run, Registry, Timed};
+use aoc19::{custom_err, Answers, AocErr, AocResult, FileType, Profile};
+use std::time::Duration;
+
+fn fmt_duration(d: Duration) -> String {
+    format!("{:.3}ms", d.as_secs_f64() * 1000.)
+}
+
+fn verdict(answer: &str, expected: Option<&str>) -> String {
+    match expected {
+        Some(expected) if expected == answer => "ok".to_string(),
+        Some(expected) if expected.contains('\n') => "WRONG".to_string(),
+        Some(expected) => format!("WRONG, expected {}", expected),
+        None => "unknown".to_string(),
+    }
+}
+
+fn print_part(part: usize, res: &Timed<AocResult<String>>, expected: Option<&str>) {
+    let time = fmt_duration(res.elapsed);
+    match &res.value {
+        Ok(answer) if answer.contains('\n') => {
+            println!(
+                "  part {}: [{}] ({})",
+                part,
+                verdict(answer, expected),
+                time
+            );
+            for line in answer.lines() {
+                println!("    {}", line);
+            }
+        }
+        Ok(answer) => println!(
+            "  part {}: {:<20} [{}] ({})",
+            part,
+            answer,
+            verdict(answer, expected),
+            time
+        ),
+        Err(err) => println!("  part {}: error: {} ({})", part, err, time),
+    }
+}
+
+const USAGE: &str =
+    "usage: aoc19 [all | <day> | <from>-<to>]... [--record] [--profile NAME | --all-profiles]";
+
+fn main() -> AocResult<()> {
+    let registry = registry();
+    let known: Vec<usize> = registry.days().collect();
+
+    let mut record = false;
+    let mut profiles = vec![Profile::current()];
+    let mut days = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => record = true,
+            "--profile" => {
+                let name = args.next().ok_or_else(|| custom_err(USAGE))?;
+                profiles = vec![Profile::named(
+                    Some(name.as_str()).filter(|n| *n != "default"),
+                )];
+            }
+            "--all-profiles" => profiles = Profile::all()?,
+            a if a.starts_with("--") => return Err(custom_err(USAGE)),
+            _ => days.push(arg),
+        }
+    }
+
+    let days = parse_days(&days, &known)?;
+    let mut total = Duration::default();
+
+    for profile in profiles.iter() {
+        println!("Profile {}", profile.display_name());
+        total += run_profile(&registry, profile, &days, record)?;
+    }
+
+    println!("Total {}", fmt_duration(total));
+    Ok(())
+}
+
+fn run_profile(
+    registry: &Registry,
+    profile: &Profile,
+    days: &[usize],
+    record: bool,
+) -> AocResult<Duration> {
+    let mut total = Duration::default();
+    for &day in days {
+        let solution = registry.get(day).unwrap();
+        let input = match profile.read_file(FileType::Input, day, 1) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("Day {:02}: no input: {}", day, err);
+                continue;
+            }
+        };
+
+        let answers = profile
+            .load_answers(FileType::Input, day, 1)
+            .unwrap_or_else(|err| {
+                println!("Day {:02}: ignoring answers file: {}", day, err);
+                Answers::default()
+            });
+
+        match run(solution, &input) {
+            Ok(r) => {
+                println!("Day {:02} (parse {})", day, fmt_duration(r.parse));
+                for (part, res) in [(1, &r.part1), (2, &r.part2)].iter() {
+                    print_part(*part, res, answers.get(*part));
+
+                    //Only unknown answers are recorded, known ones are never overwritten
+                    if let (true, None, Ok(answer)) = (record, answers.get(*part), &res.value) {
+                        profile.record_answer(FileType::Input, day, 1, *part, answer.clone())?;
+                        println!("  part {}: recorded", part);
+                    }
+                }
+                total += r.parse + r.part1.elapsed + r.part2.elapsed;
+            }
+            Err(err) => match in_file(err, &profile.file_path(FileType::Input, day, 1)) {
+                AocErr::Parse(err) => println!("Day {:02}: parse error\n{}", day, err.render()),
+                err => println!("Day {:02}: parse error: {}", day, err),
+            },
+        }
+    }
+
+    Ok(total)
+}
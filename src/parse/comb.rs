@@ -0,0 +1,352 @@
+use super::ParseCtx;
+use crate::*;
+use std::fmt::Display;
+
+//Position in a puzzle input, every combinator reports errors relative to the whole input
+#[derive(Clone, Copy)]
+pub struct Cursor<'a> {
+    ctx: ParseCtx<'a>,
+    rest: &'a str,
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Cursor {
+            ctx: ParseCtx::new(input),
+            rest: input,
+        }
+    }
+
+    //Runs `f` on the whole input, only trailing whitespace may be left over
+    pub fn complete<T>(
+        input: &'a str,
+        f: impl FnOnce(&mut Cursor<'a>) -> AocResult<T>,
+    ) -> AocResult<T> {
+        let mut c = Cursor::new(input);
+        let v = f(&mut c)?;
+        c.eof()?;
+        Ok(v)
+    }
+
+    fn sub(&self, s: &'a str) -> Cursor<'a> {
+        Cursor {
+            ctx: self.ctx,
+            rest: s,
+        }
+    }
+
+    fn offset(&self, s: &str) -> usize {
+        s.as_ptr() as usize - self.ctx.input().as_ptr() as usize
+    }
+
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    //Error pointing at the next whitespace separated token
+    pub fn error<S: ToString>(&self, msg: S) -> AocErr {
+        let len = match self.rest.find(char::is_whitespace) {
+            Some(0) => self.peek().map_or(0, |c| c.len_utf8()),
+            Some(len) => len,
+            None => self.rest.len(),
+        };
+        self.ctx.error(&self.rest[..len], msg)
+    }
+
+    pub fn error_at<S: ToString>(&self, at: &str, msg: S) -> AocErr {
+        self.ctx.error(at, msg)
+    }
+
+    pub fn eof(&mut self) -> AocResult<()> {
+        self.take_while(char::is_whitespace);
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("Unexpected trailing text"))
+        }
+    }
+
+    pub fn take_while(&mut self, mut f: impl FnMut(char) -> bool) -> &'a str {
+        let end = self.rest.find(|c| !f(c)).unwrap_or(self.rest.len());
+        let (s, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        s
+    }
+
+    //Skips spaces and tabs but not line breaks
+    pub fn ws(&mut self) -> &mut Self {
+        self.take_while(|c| c == ' ' || c == '\t');
+        self
+    }
+
+    pub fn eat(&mut self, tag: &str) -> bool {
+        if self.rest.starts_with(tag) {
+            self.rest = &self.rest[tag.len()..];
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn tag(&mut self, tag: &str) -> AocResult<()> {
+        if self.eat(tag) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected `{}`", tag)))
+        }
+    }
+
+    fn number<T: FromStr>(&mut self, signed: bool) -> AocResult<T>
+    where
+        <T as FromStr>::Err: Display,
+    {
+        let start = *self;
+        if signed && !self.eat("-") {
+            self.eat("+");
+        }
+
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        let token = &start.rest[..start.rest.len() - self.rest.len()];
+
+        if digits.is_empty() {
+            *self = start;
+            return Err(self.error("Expected a number"));
+        }
+
+        //`12ab` is a broken number, not a number followed by a name
+        if self.peek().is_some_and(is_ident) {
+            let tail = self.take_while(is_ident);
+            let len = token.len() + tail.len();
+            return Err(self.error_at(&start.rest[..len], "Invalid number"));
+        }
+
+        self.ctx.parse(token)
+    }
+
+    pub fn uint<T: FromStr>(&mut self) -> AocResult<T>
+    where
+        <T as FromStr>::Err: Display,
+    {
+        self.number(false)
+    }
+
+    pub fn int<T: FromStr>(&mut self) -> AocResult<T>
+    where
+        <T as FromStr>::Err: Display,
+    {
+        self.number(true)
+    }
+
+    //ASCII letters, digits and `_`
+    pub fn ident(&mut self) -> AocResult<&'a str> {
+        match self.take_while(is_ident) {
+            "" => Err(self.error("Expected a name")),
+            s => Ok(s),
+        }
+    }
+
+    //Rest of the current line, the line break is consumed
+    pub fn line(&mut self) -> &'a str {
+        let line = self.take_while(|c| c != '\n');
+        self.eat("\n");
+        line.trim_end_matches('\r')
+    }
+
+    //One or more items, whitespace around `sep` is skipped
+    pub fn sep_by<T>(
+        &mut self,
+        sep: &str,
+        mut item: impl FnMut(&mut Cursor<'a>) -> AocResult<T>,
+    ) -> AocResult<Vec<T>> {
+        let mut v = vec![item(self)?];
+        loop {
+            let before = *self;
+            if !self.ws().eat(sep) {
+                *self = before;
+                return Ok(v);
+            }
+
+            self.ws();
+            v.push(item(self)?);
+        }
+    }
+
+    //`key=value` pairs separated by commas, keys have to appear in the given order
+    pub fn record<T>(
+        &mut self,
+        keys: &[&str],
+        mut value: impl FnMut(&mut Cursor<'a>) -> AocResult<T>,
+    ) -> AocResult<Vec<T>> {
+        let mut v = Vec::with_capacity(keys.len());
+        for (ix, key) in keys.iter().enumerate() {
+            if ix > 0 {
+                self.ws().tag(",")?;
+            }
+
+            let name = self.ws().ident()?;
+            if name != *key {
+                return Err(self.error_at(name, format!("Expected key `{}`", key)));
+            }
+
+            self.ws().tag("=")?;
+            v.push(value(self.ws())?);
+        }
+
+        Ok(v)
+    }
+
+    //Runs `item` on every non-empty line, each line has to be consumed completely
+    pub fn lines<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Cursor<'a>) -> AocResult<T>,
+    ) -> AocResult<Vec<T>> {
+        let mut v = Vec::new();
+        while !self.is_empty() {
+            let line = self.line();
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut c = self.sub(line);
+            v.push(item(&mut c)?);
+            c.eof()?;
+        }
+
+        Ok(v)
+    }
+
+    //Rectangular block of cells up to the next blank line
+    pub fn grid<T>(&mut self, mut cell: impl FnMut(char) -> Option<T>) -> AocResult<Vec<Vec<T>>> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        while !self.is_empty() {
+            let line = self.line();
+            if line.trim().is_empty() {
+                break;
+            }
+
+            let mut row = Vec::with_capacity(line.len());
+            for (ix, c) in line.char_indices() {
+                let at = &line[ix..ix + c.len_utf8()];
+                row.push(cell(c).ok_or_else(|| self.error_at(at, "Invalid cell"))?);
+            }
+
+            match rows.first() {
+                Some(first) if first.len() != row.len() => {
+                    let msg = format!("Expected {} cells, got {}", first.len(), row.len());
+                    return Err(self.error_at(line, msg));
+                }
+                _ => rows.push(row),
+            }
+        }
+
+        if rows.is_empty() {
+            return Err(self.error("Expected a grid"));
+        }
+        Ok(rows)
+    }
+
+    //Blocks of lines separated by blank lines
+    pub fn sections(&mut self) -> Vec<Cursor<'a>> {
+        let mut sections = Vec::new();
+        while !self.is_empty() {
+            let start = self.offset(self.rest);
+            let mut end = start;
+
+            while !self.is_empty() {
+                let line = self.line();
+                if line.trim().is_empty() {
+                    break;
+                }
+                end = self.offset(line) + line.len();
+            }
+
+            if end > start {
+                sections.push(self.sub(&self.ctx.input()[start..end]));
+            }
+        }
+
+        sections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn located(err: AocErr) -> String {
+        match err {
+            AocErr::Parse(err) => format!("{}:{} {}", err.line, err.col, err.text()),
+            err => panic!("not a parse error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_values() -> AocResult<()> {
+        let mut c = Cursor::new("-12 +3 7 COM_1)B");
+        assert_eq!(-12, c.int::<isize>()?);
+        assert_eq!(3, c.ws().int::<isize>()?);
+        assert_eq!(7, c.ws().uint::<u8>()?);
+        assert!(c.ws().uint::<usize>().is_err());
+        assert_eq!("COM_1", c.ident()?);
+        c.tag(")")?;
+        assert_eq!("B", c.ident()?);
+        c.eof()?;
+
+        let err = Cursor::complete("1\n22x y", |c| c.lines(|c| c.uint::<u32>()));
+        assert_eq!("2:1 22x", located(err.err().unwrap()));
+        let err = Cursor::complete("300", |c| c.uint::<u8>());
+        assert_eq!("1:1 300", located(err.err().unwrap()));
+        let err = Cursor::complete("1 2", |c| c.uint::<u8>());
+        assert_eq!("1:3 2", located(err.err().unwrap()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lists() -> AocResult<()> {
+        let v = Cursor::complete("1, 2 ,3", |c| c.sep_by(",", |c| c.uint::<u8>()))?;
+        assert_eq!(vec![1, 2, 3], v);
+
+        let v = Cursor::complete("x=1, y=-2", |c| c.record(&["x", "y"], |c| c.int::<i8>()))?;
+        assert_eq!(vec![1, -2], v);
+        let err = Cursor::complete("x=1, z=2", |c| c.record(&["x", "y"], |c| c.int::<i8>()));
+        assert_eq!("1:6 z", located(err.err().unwrap()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocks() -> AocResult<()> {
+        let input = "#.\n.#\n\n\nab\ncd\n";
+        let mut c = Cursor::new(input);
+        let sections = c.sections();
+        assert_eq!(2, sections.len());
+        assert_eq!("ab\ncd", sections[1].rest());
+
+        let grid = { sections[0] }.grid(|c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        assert_eq!(vec![vec![true, false], vec![false, true]], grid);
+
+        let err = { sections[1] }.grid(|c| if c == 'a' { Some(c) } else { None });
+        assert_eq!("5:2 b", located(err.err().unwrap()));
+        let err = Cursor::new("ab\nabc").grid(Some);
+        assert_eq!("2:1 abc", located(err.err().unwrap()));
+
+        Ok(())
+    }
+}
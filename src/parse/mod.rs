@@ -0,0 +1,194 @@
+pub mod comb;
+
+pub use comb::Cursor;
+use crate::*;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+//Line and column are 1-based, `len` counts the chars of the offending text
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub col: usize,
+    pub len: usize,
+    pub source_line: String,
+    pub msg: String,
+}
+
+impl ParseError {
+    pub fn text(&self) -> String {
+        self.source_line
+            .chars()
+            .skip(self.col - 1)
+            .take(self.len)
+            .collect()
+    }
+
+    pub fn location(&self) -> String {
+        let file = self
+            .file
+            .as_ref()
+            .map_or("<input>".to_string(), |p| p.display().to_string());
+        format!("{}:{}:{}", file, self.line, self.col)
+    }
+
+    //Multi line diagnostic pointing at the offending text
+    pub fn render(&self) -> String {
+        let num = self.line.to_string();
+        let pad = " ".repeat(num.len());
+        format!(
+            "error: {}\n{}--> {}\n{} |\n{} | {}\n{} | {}{}",
+            self.msg,
+            pad,
+            self.location(),
+            pad,
+            num,
+            self.source_line,
+            pad,
+            " ".repeat(self.col - 1),
+            "^".repeat(self.len.max(1))
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location(), self.msg)?;
+        match self.text() {
+            t if t.is_empty() => Ok(()),
+            t => write!(f, " at `{}`", t),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+//Builds located errors for sub slices of the text being parsed
+#[derive(Clone, Copy)]
+pub struct ParseCtx<'a> {
+    input: &'a str,
+}
+
+impl<'a> ParseCtx<'a> {
+    pub fn new(input: &'a str) -> Self {
+        ParseCtx { input }
+    }
+
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    fn offset(&self, at: &str) -> usize {
+        let start = self.input.as_ptr() as usize;
+        let pos = at.as_ptr() as usize;
+
+        if pos >= start && pos + at.len() <= start + self.input.len() {
+            pos - start
+        } else {
+            //Not a slice of the input, fall back to searching for it
+            self.input.find(at).unwrap_or(0)
+        }
+    }
+
+    pub fn locate<S: ToString>(&self, at: &str, msg: S) -> ParseError {
+        let offset = self.offset(at);
+        let line_start = self.input[..offset].rfind('\n').map_or(0, |ix| ix + 1);
+        let line_end = self.input[offset..]
+            .find('\n')
+            .map_or(self.input.len(), |ix| offset + ix);
+        let source_line = self.input[line_start..line_end].trim_end_matches('\r');
+
+        ParseError {
+            file: None,
+            line: self.input[..line_start].matches('\n').count() + 1,
+            col: self.input[line_start..offset].chars().count() + 1,
+            len: at.chars().count().min(source_line.chars().count()),
+            source_line: source_line.to_string(),
+            msg: msg.to_string(),
+        }
+    }
+
+    pub fn error<S: ToString>(&self, at: &str, msg: S) -> AocErr {
+        AocErr::Parse(self.locate(at, msg))
+    }
+
+    pub fn parse<T: FromStr>(&self, at: &str) -> AocResult<T>
+    where
+        <T as FromStr>::Err: fmt::Display,
+    {
+        at.parse()
+            .map_err(|err: T::Err| self.error(at, format!("invalid value: {}", err)))
+    }
+}
+
+fn into_aoc_err<E: Error + 'static>(err: E) -> AocErr {
+    let err: Box<dyn Error> = Box::new(err);
+    match err.downcast::<AocErr>() {
+        Ok(err) => *err,
+        Err(err) => AocErr::Other(err),
+    }
+}
+
+//Errors of a parser run on line `line` (0-based) of a larger input
+pub fn on_line<E: Error + 'static>(err: E, line: usize, text: &str) -> AocErr {
+    match into_aoc_err(err) {
+        AocErr::Parse(mut err) => {
+            err.line += line;
+            AocErr::Parse(err)
+        }
+        err => {
+            let mut located = ParseCtx::new(text).locate(text, err.to_string());
+            located.line += line;
+            AocErr::Parse(located)
+        }
+    }
+}
+
+//Errors of a parser run on the contents of `path`
+pub fn in_file<E: Error + 'static>(err: E, path: &Path) -> AocErr {
+    match into_aoc_err(err) {
+        AocErr::Parse(mut err) => {
+            err.file.get_or_insert_with(|| path.to_path_buf());
+            AocErr::Parse(err)
+        }
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_err(err: AocErr) -> ParseError {
+        match err {
+            AocErr::Parse(err) => err,
+            err => panic!("not a parse error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_error() {
+        let input = "1 A\n10 ORX => 1 B\n";
+        let ctx = ParseCtx::new(input);
+        let line = input.lines().nth(1).unwrap();
+
+        let err = parse_err(ctx.parse::<usize>(&line[3..6]).unwrap_err());
+        assert_eq!((2, 4, 3), (err.line, err.col, err.len));
+        assert_eq!("ORX", err.text());
+        assert!(err.to_string().starts_with("<input>:2:4: invalid value"));
+        assert!(err.render().ends_with("2 | 10 ORX => 1 B\n  |    ^^^"));
+    }
+
+    #[test]
+    fn test_line_vec() {
+        let err = "1\n2\n3x"
+            .parse::<ParseLineVec<usize>>()
+            .map(|v| v.0)
+            .unwrap_err();
+        let err = parse_err(in_file(err, Path::new("data/x.data")));
+        assert_eq!("data/x.data:3:1", err.location());
+        assert_eq!("3x", err.text());
+    }
+}
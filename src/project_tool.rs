@@ -0,0 +1,187 @@
+use aoc19::{custom_err, AocResult, FileType, Profile};
+use itertools::Itertools;
+use std::fs;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: project new-day <day>
+       project migrate-data [--dry-run]";
+
+const TEMPLATE: &str = include_str!("days/day_tmpl.rs");
+
+fn days_dir() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "src", "days"].iter().collect()
+}
+
+fn day_module(day: usize) -> String {
+    TEMPLATE
+        .replace("Day00", &format!("Day{:02}", day))
+        .replace(
+            "const DAY: usize = 0;",
+            &format!("const DAY: usize = {};", day),
+        )
+}
+
+//Inserts `line` into the block of lines starting with `prefix`, keeping it sorted
+fn insert_sorted(src: &str, prefix: &str, line: &str) -> AocResult<String> {
+    let mut lines: Vec<&str> = src.lines().collect();
+    if lines.contains(&line) {
+        return Err(custom_err(format!("Already present: {}", line.trim())));
+    }
+
+    let block: Vec<usize> = (0..lines.len())
+        .filter(|&ix| lines[ix].starts_with(prefix))
+        .collect();
+    let last = *block
+        .last()
+        .ok_or_else(|| custom_err(format!("No `{}` lines found", prefix.trim())))?;
+    let pos = block
+        .into_iter()
+        .find(|&ix| lines[ix] > line)
+        .unwrap_or(last + 1);
+
+    lines.insert(pos, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn register_day(mod_rs: &str, day: usize) -> AocResult<String> {
+    let src = insert_sorted(mod_rs, "pub mod day", &format!("pub mod day{:02};", day))?;
+    insert_sorted(
+        &src,
+        "    r.register::<",
+        &format!("    r.register::<day{0:02}::Day{0:02}>();", day),
+    )
+}
+
+fn new_day(day: usize) -> AocResult<()> {
+    if !(1..=25).contains(&day) {
+        return Err(custom_err(format!("Invalid day: {}", day)));
+    }
+
+    let module = days_dir().join(format!("day{:02}.rs", day));
+    if module.exists() {
+        return Err(custom_err(format!("{} already exists", module.display())));
+    }
+
+    let mod_rs = days_dir().join("mod.rs");
+    let registered = register_day(&fs::read_to_string(&mod_rs)?, day)?;
+
+    fs::write(&module, day_module(day))?;
+    fs::write(&mod_rs, registered)?;
+    println!("created {}", module.display());
+    println!("registered day {} in {}", day, mod_rs.display());
+
+    let profile = Profile::current();
+    fs::create_dir_all(profile.day_path(day))?;
+    let example = profile.file_path(FileType::Example, day, 1);
+    if !example.exists() {
+        fs::write(&example, "")?;
+        println!("created {}", example.display());
+    }
+
+    Ok(())
+}
+
+//`in_09_01.data` -> `in_01.data`, the day moved into the directory name
+fn new_name(name: &str) -> Option<String> {
+    if name.split('_').count() != 3 {
+        return None;
+    }
+
+    Some(
+        name.split('_')
+            .enumerate()
+            .filter_map(|(ix, s)| if ix != 1 { Some(s) } else { None })
+            .join("_"),
+    )
+}
+
+fn migrate_data(dry_run: bool) -> AocResult<()> {
+    //Only the default profile ever used the old names
+    let data = Profile::named(None).path();
+    for day in data.read_dir()? {
+        let day = day?;
+        let is_day = day
+            .file_name()
+            .to_str()
+            .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()));
+        if !is_day || !day.file_type()?.is_dir() {
+            continue;
+        }
+
+        for file in day.path().read_dir()? {
+            let file = file?.path();
+            let name = file.file_name().unwrap().to_string_lossy().into_owned();
+            let new_path = match new_name(&name) {
+                Some(new) => file.with_file_name(new),
+                None => continue,
+            };
+
+            if new_path.exists() {
+                println!("{}: target exists, skipped", file.display());
+                continue;
+            }
+
+            println!("{} -> {}", file.display(), new_path.display());
+            if !dry_run {
+                fs::rename(file, new_path)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> AocResult<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+
+    match args.as_slice() {
+        ["new-day", day] => new_day(
+            day.parse()
+                .map_err(|_| custom_err(format!("Invalid day: {}", day)))?,
+        ),
+        ["migrate-data"] => migrate_data(false),
+        ["migrate-data", "--dry-run"] => migrate_data(true),
+        _ => Err(custom_err(USAGE)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename() {
+        assert_eq!(Some("in_01.data".to_string()), new_name("in_01_01.data"));
+        assert_eq!(Some("in_01.data".to_string()), new_name("in_09_01.data"));
+        assert_eq!(None, new_name("in_01.data"));
+    }
+
+    #[test]
+    fn test_register() -> AocResult<()> {
+        let mod_rs = "pub mod day01;\npub mod day03;\n\npub fn registry() -> Registry {\n    \
+                      let mut r = Registry::default();\n    r.register::<day01::Day01>();\n    \
+                      r.register::<day03::Day03>();\n    r\n}\n";
+
+        let src = register_day(mod_rs, 2)?;
+        assert!(src.starts_with("pub mod day01;\npub mod day02;\npub mod day03;\n"));
+        assert!(
+            src.contains("Day01>();\n    r.register::<day02::Day02>();\n    r.register::<day03")
+        );
+        assert!(register_day(&src, 2).is_err());
+
+        let src = register_day(mod_rs, 4)?;
+        assert!(src.contains("pub mod day03;\npub mod day04;\n\n"));
+        assert!(src.contains("r.register::<day04::Day04>();\n    r\n}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_template() {
+        let module = day_module(15);
+        assert!(module.contains("pub struct Day15;"));
+        assert!(module.contains("impl Solution for Day15 {"));
+        assert!(module.contains("const DAY: usize = 15;"));
+    }
+}
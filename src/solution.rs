@@ -0,0 +1,209 @@
+use crate::*;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+pub trait Solution {
+    const DAY: usize;
+    type Data: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> AocResult<Self::Data>;
+    fn part1(data: &Self::Data) -> AocResult<Self::Answer1>;
+    fn part2(data: &Self::Data) -> AocResult<Self::Answer2>;
+}
+
+//Object safe view of a `Solution` so days can live in one registry
+pub trait DynSolution {
+    fn day(&self) -> usize;
+    fn parse(&self, input: &str) -> AocResult<Box<dyn Any>>;
+    fn part1(&self, data: &dyn Any) -> AocResult<String>;
+    fn part2(&self, data: &dyn Any) -> AocResult<String>;
+}
+
+struct Erased<S>(PhantomData<S>);
+
+fn downcast<S: Solution>(data: &dyn Any) -> AocResult<&S::Data> {
+    data.downcast_ref()
+        .ok_or_else(|| custom_err(format!("Data of wrong type for day {}", S::DAY)))
+}
+
+impl<S: Solution> DynSolution for Erased<S> {
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> AocResult<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, data: &dyn Any) -> AocResult<String> {
+        Ok(S::part1(downcast::<S>(data)?)?.to_string())
+    }
+
+    fn part2(&self, data: &dyn Any) -> AocResult<String> {
+        Ok(S::part2(downcast::<S>(data)?)?.to_string())
+    }
+}
+
+#[derive(Default)]
+pub struct Registry(BTreeMap<usize, Box<dyn DynSolution>>);
+
+impl Registry {
+    pub fn register<S: Solution + 'static>(&mut self) {
+        self.0.insert(S::DAY, Box::new(Erased::<S>(PhantomData)));
+    }
+
+    pub fn get(&self, day: usize) -> Option<&dyn DynSolution> {
+        self.0.get(&day).map(|s| s.as_ref())
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.keys().cloned()
+    }
+}
+
+pub struct Timed<T> {
+    pub value: T,
+    pub elapsed: Duration,
+}
+
+pub fn timed<T>(f: impl FnOnce() -> T) -> Timed<T> {
+    let start = Instant::now();
+    let value = f();
+    Timed {
+        value,
+        elapsed: start.elapsed(),
+    }
+}
+
+pub struct Run {
+    pub parse: Duration,
+    pub part1: Timed<AocResult<String>>,
+    pub part2: Timed<AocResult<String>>,
+}
+
+pub fn run(solution: &dyn DynSolution, input: &str) -> AocResult<Run> {
+    let data = timed(|| solution.parse(input));
+    let parse = data.elapsed;
+    let data = data.value?;
+
+    Ok(Run {
+        parse,
+        part1: timed(|| solution.part1(data.as_ref())),
+        part2: timed(|| solution.part2(data.as_ref())),
+    })
+}
+
+//Accepts `all`, single days and ranges like `3-7`, empty means all
+pub fn parse_days(args: &[String], known: &[usize]) -> AocResult<Vec<usize>> {
+    if args.is_empty() {
+        return Ok(known.to_vec());
+    }
+
+    let mut days = Vec::new();
+    for arg in args {
+        if arg == "all" {
+            days.extend_from_slice(known);
+            continue;
+        }
+
+        let (from, to) = match arg.find('-') {
+            Some(ix) => (&arg[..ix], &arg[ix + 1..]),
+            None => (arg.as_str(), arg.as_str()),
+        };
+
+        let parse = |s: &str| {
+            s.parse::<usize>()
+                .map_err(|_| custom_err(format!("Invalid day: {}", arg)))
+        };
+        let (from, to) = (parse(from)?, parse(to)?);
+        days.extend(known.iter().filter(|&&d| d >= from && d <= to));
+    }
+
+    if days.is_empty() {
+        return Err(custom_err("No matching days"));
+    }
+
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dummy;
+
+    impl Solution for Dummy {
+        const DAY: usize = 42;
+        type Data = Vec<u8>;
+        type Answer1 = usize;
+        type Answer2 = String;
+
+        fn parse(input: &str) -> AocResult<Vec<u8>> {
+            Ok(input.bytes().collect())
+        }
+
+        fn part1(data: &Vec<u8>) -> AocResult<usize> {
+            Ok(data.len())
+        }
+
+        fn part2(_: &Vec<u8>) -> AocResult<String> {
+            Err(custom_err("unsolved"))
+        }
+    }
+
+    #[test]
+    fn test_registry() -> AocResult<()> {
+        let mut registry = Registry::default();
+        registry.register::<Dummy>();
+
+        assert_eq!(vec![42], registry.days().collect::<Vec<_>>());
+        assert!(registry.get(1).is_none());
+
+        let run = run(registry.get(42).unwrap(), "abc")?;
+        assert_eq!("3", run.part1.value?);
+        assert!(run.part2.value.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_days() -> AocResult<()> {
+        let known = [1, 2, 3, 5, 8];
+        let args = |s: &str| s.split(' ').map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(known.to_vec(), parse_days(&[], &known)?);
+        assert_eq!(vec![2, 3, 5, 8], parse_days(&args("2-6 8"), &known)?);
+        assert!(parse_days(&args("x"), &known).is_err());
+        assert!(parse_days(&args("9"), &known).is_err());
+
+        Ok(())
+    }
+
+    //The default profile is covered by the part tests of each day
+    #[test]
+    fn test_profiles() -> AocResult<()> {
+        let registry = crate::days::registry();
+        for profile in Profile::all()?.iter().filter(|p| p.name.is_some()) {
+            for day in registry.days() {
+                let input = match profile.read_file(FileType::Input, day, 1) {
+                    Ok(input) => input,
+                    Err(AocErr::IoError(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+                        continue
+                    }
+                    Err(err) => return Err(err),
+                };
+
+                let run = run(registry.get(day).unwrap(), &input)?;
+                profile.check_answer(FileType::Input, day, 1, 1, run.part1.value?)?;
+                profile.check_answer(FileType::Input, day, 1, 2, run.part2.value?)?;
+            }
+        }
+
+        Ok(())
+    }
+}